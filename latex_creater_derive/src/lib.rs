@@ -0,0 +1,203 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Fields, Lit, LitStr, Path, Result,
+    Type,
+};
+
+//derives `ToTableRow`, one column per field
+//
+//on the struct `#[table(crate = "path")]` sets where the latex module lives,
+//`::latex_creater::latex` by default
+//on a field:
+//  `#[table(title = "Time")]` the header of the column, the field name by default
+//  `#[table(format = "{:.2}")]` the format string for the value, `{}` by default
+//  `#[table(align = "r")]` one of `l`, `c`, `r` and `S` for decimal alignment,
+//      numbers are right aligned and everything else left aligned by default
+//  `#[table(width = 3.5)]` a paragraph column of this width in cm, greater than 0
+//  `#[table(skip)]` leaves the field out
+#[proc_macro_derive(ToTableRow, attributes(table))]
+pub fn derive_to_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_table_row(input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+fn to_table_row(input: DeriveInput) -> Result<TokenStream2> {
+    let mut crate_path: Path = syn::parse_quote!(::latex_creater::latex);
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("table")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                crate_path = meta.value()?.parse::<LitStr>()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "ToTableRow needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ToTableRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut columns = Vec::new();
+    let mut headers = Vec::new();
+    let mut cells = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have names");
+        let column = Column::parse(field)?;
+        if column.skip {
+            continue;
+        }
+
+        let title = column.title.unwrap_or_else(|| ident.to_string());
+        let format = column.format.unwrap_or_else(|| "{}".to_owned());
+        let alignment = match (column.width, column.align.as_deref()) {
+            (Some(width), _) => quote!(#crate_path::ColumnAlignment::Paragraph(#width)),
+            (None, Some("l")) => quote!(#crate_path::ColumnAlignment::Left),
+            (None, Some("c")) => quote!(#crate_path::ColumnAlignment::Center),
+            (None, Some("r")) => quote!(#crate_path::ColumnAlignment::Right),
+            (None, Some("S")) => quote!(#crate_path::ColumnAlignment::Decimal),
+            (None, Some(_)) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "expected one of `l`, `c`, `r` and `S` as alignment",
+                ))
+            }
+            (None, None) if is_number(&field.ty) => quote!(#crate_path::ColumnAlignment::Right),
+            (None, None) => quote!(#crate_path::ColumnAlignment::Left),
+        };
+
+        columns.push(alignment);
+        headers.push(quote!(#crate_path::Cell::from(#title)));
+        cells.push(quote!(#crate_path::Cell::from(::std::format!(#format, self.#ident))));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #crate_path::ToTableRow for #name #type_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<#crate_path::ColumnAlignment> {
+                ::std::vec![#(#columns),*]
+            }
+
+            fn header() -> ::std::vec::Vec<#crate_path::Cell> {
+                ::std::vec![#(#headers),*]
+            }
+
+            fn row(&self) -> ::std::vec::Vec<#crate_path::Cell> {
+                ::std::vec![#(#cells),*]
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct Column {
+    title: Option<String>,
+    format: Option<String>,
+    align: Option<String>,
+    width: Option<f32>,
+    skip: bool,
+}
+
+impl Column {
+    fn parse(field: &syn::Field) -> Result<Self> {
+        let mut column = Column::default();
+        for attribute in field.attrs.iter().filter(|a| a.path().is_ident("table")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("title") {
+                    column.title = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("format") {
+                    column.format = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("align") {
+                    column.align = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("width") {
+                    column.width = Some(parse_width(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("skip") {
+                    column.skip = true;
+                } else {
+                    return Err(
+                        meta.error("expected `title`, `format`, `align`, `width` or `skip`")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(column)
+    }
+}
+
+//checked here, so a bad width fails the build instead of the export
+fn parse_width(expr: &Expr) -> Result<f32> {
+    let width = match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(float),
+            ..
+        }) => float.base10_parse::<f32>()?,
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse::<f32>()?,
+        _ => {
+            return Err(Error::new_spanned(
+                expr,
+                "expected the width in cm as a number",
+            ))
+        }
+    };
+    if width.is_finite() && width > 0.0 {
+        Ok(width)
+    } else {
+        Err(Error::new_spanned(
+            expr,
+            "the width has to be greater than 0",
+        ))
+    }
+}
+
+fn is_number(ty: &Type) -> bool {
+    const NUMBERS: [&str; 14] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+    match ty {
+        Type::Path(path) => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| NUMBERS.iter().any(|number| ident == number)),
+        Type::Reference(reference) => is_number(&reference.elem),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_width;
+    use syn::parse_quote;
+
+    #[test]
+    fn widths_are_checked() {
+        assert_eq!(parse_width(&parse_quote!(3.5)).unwrap(), 3.5);
+        assert_eq!(parse_width(&parse_quote!(2)).unwrap(), 2.0);
+        assert!(parse_width(&parse_quote!(0.0)).is_err());
+        assert!(parse_width(&parse_quote!(-1.5)).is_err());
+        assert!(parse_width(&parse_quote!("wide")).is_err());
+        assert!(parse_width(&parse_quote!(width)).is_err());
+    }
+}
@@ -1,19 +1,68 @@
 use lazy_static::lazy_static;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use std::hash::Hash;
 
 use std::ops::{Deref, DerefMut};
 mod tikz;
 use derive_more::From;
 pub use tikz::{
-    AddOption, AddPoint, AddPointByParts, Color, Line, LineOption, Node, NodeOptions, Polygon,
-    PolygonOption, Tikz, TikzError, TikzOption, TikzPart,
+    AddOption, AddPoint, AddPointByParts, Anchor, Arc, Arrow, ArrowDirection, ArrowTip, BaseColor,
+    Brewer, BrewerKind, Circle, Color, DashPattern, Direction, Ellipse, Line, LineCap, LineJoin,
+    LineOption, NamedColor, Node, NodeOptions, NodeShape, OverlayMode, OverlaySpec, Paint, Palette,
+    Polygon, PolygonOption, Rectangle, RelativePosition, ShapeOption, Tikz, TikzError, TikzOption,
+    TikzPart, Visible, KIT_BLACK, KIT_BLACK_70, KIT_BLUE, KIT_BROWN, KIT_CYAN, KIT_GREEN,
+    KIT_MAY_GREEN, KIT_ORANGE, KIT_PURPLE, KIT_RED, KIT_YELLOW,
 };
 
+mod font_size;
+pub use font_size::FontSize;
+
 mod error;
 pub use error::{LatexError, LatexResult};
 
+mod text;
+pub use text::{escape, RefKind, Style, Text, TextSpan};
+
+mod label;
+pub use label::Label;
+
+mod section;
+pub use section::{Section, SectionLevel};
+
+mod list;
+pub use list::{EnumerateLabel, Item, List, ListKind, Numbering};
+
+mod table;
+pub use latex_creater_derive::ToTableRow;
+pub use table::{Cell, ColumnAlignment, Table, ToTableRow};
+
+mod math;
+pub use math::{Align, AlignRow, Equation, Greek, MathExpr, MatrixKind};
+
+mod figure;
+pub use figure::{Figure, Image, Placement};
+
+mod beamer;
+pub use beamer::{Block, BlockKind, Frame, FrameOption};
+
+mod theme;
+pub use theme::{BeamerColor, BeamerTheme, Footline};
+
+mod layout;
+pub use layout::{Column, Columns, ColumnsKind, SubFigure, VerticalAlignment, Width};
+
+mod document_class;
+pub use document_class::{AspectRatio, ClassOption, DocumentClass, Paper};
+
+mod package;
+pub use package::{Package, Preamble};
+
+mod lint;
+pub use lint::{Lint, MIN_CONTRAST_RATIO};
+
+mod option_set;
+pub use option_set::{NamedOption, OptionSet};
+
 mod to_latex;
 pub use to_latex::ToLatex;
 
@@ -27,20 +76,192 @@ lazy_static! {
 pub struct Latex {
     document_class: DocumentClass,
     parts: Vec<LatexPart>,
+    class_options: OptionSet<ClassOption>,
+    strict_options: bool,
+    preamble: Preamble,
+    beamer_theme: Option<BeamerTheme>,
 }
 
 impl Latex {
     pub fn new(document_class: DocumentClass) -> Self {
         Latex {
             document_class,
+            class_options: OptionSet::new(),
             parts: Vec::new(),
+            strict_options: false,
+            preamble: Preamble::new(),
+            beamer_theme: None,
         }
     }
 
+    pub fn class_option(mut self, option: ClassOption) -> Self {
+        self.class_options.insert(option);
+        self
+    }
+
+    //by default a later option replaces an earlier one of the same kind,
+    //in strict mode setting an option twice with different values fails the export
+    pub fn strict_options(mut self) -> Self {
+        self.strict_options = true;
+        self
+    }
+
     pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
         self.parts.push(part.into());
         self
     }
+
+    //loaded in addition to the packages the parts need, options are merged with theirs
+    pub fn package(mut self, package: Package) -> Self {
+        self.preamble.package(package);
+        self
+    }
+
+    //only for beamer
+    pub fn beamer_theme(mut self, theme: BeamerTheme) -> Self {
+        self.beamer_theme = Some(theme);
+        self
+    }
+
+    //a raw line at the end of the preamble
+    pub fn preamble_line(mut self, line: impl Into<String>) -> Self {
+        self.preamble.line(line);
+        self
+    }
+
+    //every rgb color used anywhere in the document, each gets one `\definecolor` named after its
+    //value, so two different colors can never share a name
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        let mut colors = BTreeSet::new();
+        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+            colors.extend(part.get_colors());
+            //also covers texts a part does not collect colors from itself
+            for text in part.texts() {
+                colors.extend(text.get_colors());
+            }
+        }
+        if let Some(theme) = &self.beamer_theme {
+            colors.extend(theme.get_colors());
+        }
+        colors
+    }
+
+    //problems that do not stop the export, like text that is hard to read on its background
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints: Vec<Lint> = self
+            .parts
+            .iter()
+            .flat_map(|part| part.iter_full())
+            .flat_map(|part| part.lints())
+            .collect();
+        if let Some(theme) = &self.beamer_theme {
+            lints.extend(theme.lints());
+        }
+        lints
+    }
+
+    //checks the structure of the document against its class before anything is written
+    fn validate(&self) -> LatexResult<()> {
+        if self.beamer_theme.is_some() && !self.document_class.allows_frames() {
+            return Err(LatexError::PartNotAllowed("beamer theme"));
+        }
+        for part in self.parts.iter() {
+            validate_part(part, &self.document_class, None, false, false)?;
+        }
+
+        //every reference needs exactly one label it points to
+        let mut labels = BTreeSet::new();
+        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+            if let Some(label) = part.unnumbered_labels().first() {
+                return Err(LatexError::UnnumberedLabel(label.name().to_owned()));
+            }
+            for label in part.labels() {
+                if !labels.insert(label) {
+                    return Err(LatexError::DuplicateLabel(label.name().to_owned()));
+                }
+            }
+        }
+        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+            for text in part.texts() {
+                for (_, label) in text.references() {
+                    if !labels.contains(label) {
+                        return Err(LatexError::DanglingReference(label.name().to_owned()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_part(
+    part: &LatexPart,
+    document_class: &DocumentClass,
+    mut parent: Option<SectionLevel>,
+    mut in_frame: bool,
+    mut in_figure: bool,
+) -> LatexResult<()> {
+    match part {
+        LatexPart::Section(section) => {
+            let level = section.level();
+            if !document_class.allows_section(level) {
+                return Err(LatexError::SectionNotAllowed(level));
+            }
+            if in_frame {
+                return Err(LatexError::SectionInFrame(level));
+            }
+            if let Some(parent) = parent.filter(|parent| *parent >= level) {
+                return Err(LatexError::InvalidSectionNesting {
+                    parent,
+                    child: level,
+                });
+            }
+            parent = Some(level);
+        }
+        LatexPart::Frame(_) if !document_class.allows_frames() => {
+            return Err(LatexError::PartNotAllowed("frame"));
+        }
+        LatexPart::Frame(_) => in_frame = true,
+        LatexPart::Figure(_) if !document_class.allows_floats() => {
+            return Err(LatexError::PartNotAllowed("figure"));
+        }
+        LatexPart::SubFigure(_) if !in_figure => return Err(LatexError::SubFigureOutsideFigure),
+        LatexPart::Columns(columns)
+            if columns.kind() == ColumnsKind::Beamer && !document_class.allows_frames() =>
+        {
+            return Err(LatexError::PartNotAllowed("beamer columns"));
+        }
+        LatexPart::Block(_) if !document_class.allows_frames() => {
+            return Err(LatexError::PartNotAllowed("block"));
+        }
+        LatexPart::Pause if !document_class.allows_overlays() => {
+            return Err(LatexError::OverlayNotSupported);
+        }
+        LatexPart::Visible(_) if !document_class.allows_overlays() => {
+            return Err(LatexError::OverlayNotSupported);
+        }
+        LatexPart::Tikz(tikz) if tikz.has_overlays() && !document_class.allows_overlays() => {
+            return Err(LatexError::OverlayNotSupported);
+        }
+        LatexPart::List(list)
+            if list.iter_items().any(|item| item.has_overlay())
+                && !document_class.allows_overlays() =>
+        {
+            return Err(LatexError::OverlayNotSupported);
+        }
+        _ if !document_class.allows_frames()
+            && part.texts().iter().any(|text| text.has_alerts()) =>
+        {
+            return Err(LatexError::PartNotAllowed("alert"));
+        }
+        _ => {}
+    }
+    in_figure |= matches!(part, LatexPart::Figure(_));
+
+    for inner in part.iter_next_inner().into_iter().flatten() {
+        validate_part(inner, document_class, parent, in_frame, in_figure)?;
+    }
+    Ok(())
 }
 
 #[derive(From, Debug)]
@@ -67,7 +288,8 @@ impl Display for LatexLines {
         if let Some(LatexLine {
             indentation,
             line_content,
-        }) = self.lines.first() {
+        }) = self.lines.first()
+        {
             for _ in 0..*indentation {
                 write!(f, "\t")?;
             }
@@ -123,48 +345,67 @@ impl Display for LatexLine {
 
 impl ToLatex for Latex {
     fn export(&self) -> LatexResult<LatexLines> {
+        self.validate()?;
+        if self.strict_options {
+            if let Some(name) = self.class_options.conflict() {
+                return Err(LatexError::ConflictingOption(name));
+            }
+            for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+                match part {
+                    LatexPart::Tikz(tikz) => tikz.check_conflicts()?,
+                    LatexPart::Frame(frame) => {
+                        if let Some(name) = frame.option_conflict() {
+                            return Err(LatexError::ConflictingOption(name));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         let mut lines = Vec::new();
 
-        let first_line = self.document_class.get_document_class_line();
+        let first_line = self
+            .document_class
+            .get_document_class_line(&self.class_options)?;
         lines.push(first_line.into());
-        let input_line = r"\usepackage[utf8] {inputenc}";
-        lines.push(input_line.to_owned().into());
 
-        if self
-            .parts
-            .iter()
-            .flat_map(|part| part.iter_full())
-            .any(|part| matches!(part, LatexPart::Tikz(_)))
-        {
-            //the tikz base package
-            let line = r"\usepackage{tikz}";
-            lines.push(line.to_owned().into());
-
-            let line = r"\usepackage{pgfplots}";
-            lines.push(line.to_owned().into());
-
-            //makes compiling faster by incremental tikz compiling
-            let line = r"\usetikzlibrary{external}";
-            lines.push(line.to_owned().into());
-            let line = r"\usepackage{color}";
-            lines.push(line.to_owned().into());
-            let line = r"\usetikzlibrary{arrows ,automata ,positioning, shapes.misc}";
-            lines.push(line.to_owned().into());
+        let mut preamble = Preamble::new();
+        preamble.package(Package::new("inputenc").option("utf8"));
+        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+            for package in part.packages() {
+                preamble.package(package);
+            }
+            for library in part.tikz_libraries() {
+                preamble.tikz_library(library);
+            }
         }
 
-        //get all colors
-        let mut colors = HashSet::new();
-        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
-            if let LatexPart::Tikz(tikz) = part {
-                for color in tikz.get_colors() {
-                    colors.insert(color);
+        let colors = self.get_colors();
+        if let Some(theme) = &self.beamer_theme {
+            for text in theme.texts() {
+                for package in text.packages() {
+                    preamble.package(package);
                 }
             }
         }
+        if !colors.is_empty() {
+            preamble.package(Package::new("xcolor"));
+        }
         for color in colors {
-            lines.push(color.get_color_definitions());
+            preamble.line(color.get_color_definitions().line_content);
+        }
+        if let Some(theme) = &self.beamer_theme {
+            for line in theme.export()?.lines {
+                preamble.line(line.line_content);
+            }
         }
 
+        //the user's packages and lines come last, so they can use everything above
+        preamble.extend(self.preamble.clone());
+        preamble.retain(|package| !self.document_class.provides_package(package.name()));
+        lines.extend(preamble.export()?.lines);
+
         let line = r"\begin{document}";
         lines.push(line.to_owned().into());
 
@@ -183,17 +424,238 @@ impl ToLatex for Latex {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub enum DocumentClass {
-    Article,
-    Beamer,
-}
+#[cfg(test)]
+mod tests {
+    use super::{
+        AddOption, AddPointByParts, Arrow, BeamerColor, BeamerTheme, Block, Cell, Color, Column,
+        ColumnAlignment, Columns, DashPattern, DocumentClass, Figure, Footline, Frame, Item, Latex,
+        LatexError, LatexPart, Line, LineOption, Lint, List, NamedColor, Node, NodeOptions,
+        NodeShape, Package, Paint, Polygon, Section, SectionLevel, SubFigure, Table, Text, Tikz,
+        TikzError, TikzOption, ToLatex, Width, KIT_BLACK, KIT_BLUE, KIT_YELLOW, MIN_CONTRAST_RATIO,
+    };
+
+    fn document() -> Latex {
+        let red = Color { r: 200, g: 0, b: 0 };
+        let blue = Color { r: 0, g: 0, b: 200 };
+        let green = Color { r: 0, g: 200, b: 0 };
+
+        let line = Line::new()
+            .option(LineOption::Width(1.5))
+            .option(blue)
+            .option(LineOption::Dash(DashPattern::Dashed))
+            .option(Arrow::end().color(green))
+            .point(0, 0)
+            .point(1, 2);
+        let polygon = Polygon::new()
+            .option(red)
+            .point(0, 0)
+            .point(1, 0)
+            .point(1, 1);
+        let node = Node::new("label")
+            .option(NodeOptions::Fill(green.into()))
+            .option(NodeOptions::Draw(red.into()))
+            .option(NodeShape::Circle)
+            .at((2, 2));
+        let tikz = Tikz::new()
+            .option(TikzOption::Scale(0.5))
+            .part(polygon)
+            .part(line)
+            .part(node);
+
+        Latex::new(DocumentClass::Article).part(LatexPart::Center(vec![tikz.into()]))
+    }
+
+    #[test]
+    fn export_is_reproducible() {
+        let first = document().export().unwrap().to_string();
+        for _ in 0..16 {
+            assert_eq!(first, document().export().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn export_is_stable() {
+        let expected = r"\documentclass[10pt]{article}
+\usepackage[utf8]{inputenc}
+\usepackage{tikz}
+\usepackage{xcolor}
+\usetikzlibrary{arrows.meta}
+\definecolor{0x0000c8}{rgb}{0, 0, 0.78431374}
+\definecolor{0x00c800}{rgb}{0, 0.78431374, 0}
+\definecolor{0xc80000}{rgb}{0.78431374, 0, 0}
+\begin{document}
+	\begin{center}
+		\begin{tikzpicture}[scale=0.5]
+			\fill[color=0xc80000] (0, 0) -- (1, 0) -- (1, 1) -- cycle;
+			\draw[line width=1.5pt, color=0x0000c8, dashed, -{To[color=0x00c800]}] (0, 0) -- (1, 2);
+			\node[fill=0x00c800, draw=0xc80000, circle] at (2, 2) {label};
+		\end{tikzpicture}
+	\end{center}
+\end{document}";
+        assert_eq!(document().export().unwrap().to_string(), expected);
+    }
+
+    #[test]
+    fn later_options_win() {
+        let red = Color { r: 200, g: 0, b: 0 };
+        let blue = Color { r: 0, g: 0, b: 200 };
+        let polygon = Polygon::new().option(red).option(blue).point(0, 0);
+        let latex = Latex::new(DocumentClass::Article).part(Tikz::new().part(polygon));
+
+        let output = latex.export().unwrap().to_string();
+        assert!(output.contains(r"\fill[color=0x0000c8] (0, 0) -- cycle;"));
+
+        let strict = latex.strict_options().export();
+        assert!(matches!(
+            strict,
+            Err(LatexError::Tikz(TikzError::ConflictingOption("color")))
+        ));
+    }
+
+    #[test]
+    fn packages_are_collected() {
+        let output = Latex::new(DocumentClass::Article)
+            .package(Package::new("hyperref").option("colorlinks=true"))
+            .part(Text::new().link("https://example.org", "site"))
+            .part(
+                Table::new()
+                    .columns([ColumnAlignment::Left])
+                    .row(["a"])
+                    .booktabs(),
+            )
+            .package(Package::new("booktabs"))
+            .preamble_line(r"\hypersetup{urlcolor=blue}")
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.starts_with(
+            r"\documentclass[10pt]{article}
+\usepackage[utf8]{inputenc}
+\usepackage{booktabs}
+\usepackage[colorlinks=true]{hyperref}
+\hypersetup{urlcolor=blue}
+\begin{document}"
+        ));
+
+        let clash = Latex::new(DocumentClass::Article)
+            .package(Package::new("hyperref").option("colorlinks=true"))
+            .package(Package::new("hyperref").option("colorlinks=false"))
+            .export();
+        assert!(matches!(
+            clash,
+            Err(LatexError::PackageOptionClash { package, option })
+                if package == "hyperref" && option == "colorlinks"
+        ));
+
+        let incompatible = Latex::new(DocumentClass::Article)
+            .package(Package::new("subfig"))
+            .part(Figure::new(SubFigure::new(Width::LineWidth(0.5))))
+            .export();
+        assert!(matches!(
+            incompatible,
+            Err(LatexError::IncompatiblePackages("subcaption", "subfig"))
+        ));
+    }
+
+    #[test]
+    fn contrast_is_linted_across_the_document() {
+        let white = Color::from_hex(0xffffff);
+        assert!((KIT_BLACK.contrast_ratio(white) - 21.0).abs() < 0.01);
+        assert_eq!(white.contrast_ratio(white), 1.0);
+
+        let tikz = Tikz::new()
+            .part(
+                Node::new("readable")
+                    .option(NodeOptions::Fill(KIT_BLUE.into()))
+                    .option(NodeOptions::Text(NamedColor::White.into())),
+            )
+            .part(
+                Node::new("faint")
+                    .option(NodeOptions::Fill(KIT_YELLOW.into()))
+                    .option(NodeOptions::Text(NamedColor::White.into())),
+            );
+        let table = Table::new()
+            .column(ColumnAlignment::Left)
+            .column(ColumnAlignment::Left)
+            .row([
+                Cell::new("plain").background(KIT_BLACK),
+                Cell::new(Text::new().colored(NamedColor::White, "white")).background(KIT_BLACK),
+            ]);
+        let lints = Latex::new(DocumentClass::Beamer)
+            .beamer_theme(BeamerTheme::kit())
+            .part(Frame::new().part(tikz).part(table))
+            .lint();
+        let locations: Vec<String> = lints
+            .iter()
+            .map(|lint| match lint {
+                Lint::LowContrast {
+                    location, ratio, ..
+                } => {
+                    assert!(*ratio < MIN_CONTRAST_RATIO);
+                    location.clone()
+                }
+            })
+            .collect();
+        assert_eq!(locations, [r#"node "faint""#, "table cell 1 in row 1"]);
+        assert!(lints[1].to_string().contains("text 0x000000 on 0x000000"));
+    }
+
+    #[test]
+    fn every_color_is_defined_once() {
+        let colors: Vec<Color> = (1..=16).map(|i| Color { r: i, g: 0, b: 0 }).collect();
+        let text = |i: usize| Text::new().colored(colors[i], "x");
+
+        let tikz = Tikz::new()
+            .part(
+                Node::new("n")
+                    .option(NodeOptions::Fill(colors[0].into()))
+                    .option(NodeOptions::Text(colors[1].into())),
+            )
+            .part(
+                Line::new()
+                    .option(colors[2])
+                    .option(Arrow::end().color(colors[3]))
+                    .point(0, 0)
+                    .point(1, 1),
+            )
+            .part(
+                Polygon::new()
+                    .option(Paint::new(colors[4]).mix(50, colors[5]))
+                    .point(0, 0)
+                    .point(1, 0),
+            );
+        let table = Table::new()
+            .column(ColumnAlignment::Left)
+            .row([Cell::new(text(6)).background(colors[7])]);
+        let frame =
+            Frame::new()
+                .title(text(8))
+                .subtitle(text(9))
+                .part(Block::plain(text(10)).part(tikz))
+                .part(List::description().item(Item::new().label(text(11))))
+                .part(Figure::new(
+                    SubFigure::new(Width::LineWidth(0.5)).caption(text(12)),
+                ))
+                .part(Columns::beamer().column(
+                    Column::new(Width::TextWidth(0.5)).part(LatexPart::Paragraph(text(13))),
+                ))
+                .part(table);
+        let theme = BeamerTheme::new()
+            .color(
+                "frametitle",
+                BeamerColor::new().fg(colors[14]).bg(colors[0]),
+            )
+            .footline(Footline::Custom(text(15)));
+        let latex = Latex::new(DocumentClass::Beamer)
+            .beamer_theme(theme)
+            .part(Section::new(SectionLevel::Section, text(0)))
+            .part(frame);
 
-impl DocumentClass {
-    fn get_document_class_line(&self) -> String {
-        match self {
-            DocumentClass::Article => "\\documentclass[10pt]{article}".to_owned(),
-            DocumentClass::Beamer => "\\documentclass{beamer}".to_owned(),
+        assert_eq!(latex.get_colors().len(), colors.len());
+        let output = latex.export().unwrap().to_string();
+        for color in colors.iter() {
+            let definition = format!(r"\definecolor{{{}}}", color.name());
+            assert_eq!(output.matches(&definition).count(), 1, "{definition}");
         }
     }
 }
@@ -0,0 +1,260 @@
+use std::collections::BTreeSet;
+use std::slice::Iter;
+
+use super::{
+    Color, LatexLines, LatexPart, LatexResult, NamedOption, OptionSet, Text, ToLatex,
+    VerticalAlignment,
+};
+
+//a slide
+#[derive(Clone, Default)]
+pub struct Frame {
+    title: Option<Text>,
+    subtitle: Option<Text>,
+    options: OptionSet<FrameOption>,
+    parts: Vec<LatexPart>,
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<Text>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn subtitle(mut self, subtitle: impl Into<Text>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn option(mut self, option: FrameOption) -> Self {
+        self.options.insert(option);
+        self
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, LatexPart> {
+        self.parts.iter()
+    }
+
+    pub fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        self.title.iter().chain(self.subtitle.iter()).collect()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.texts()
+            .iter()
+            .flat_map(|text| text.get_colors())
+            .collect()
+    }
+}
+
+impl ToLatex for Frame {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut begin = r"\begin{frame}".to_owned();
+        if !self.options.is_empty() {
+            begin.push_str(&format!("[{}]", self.options.export_list()?));
+        }
+
+        let mut inner_lines = Vec::new();
+        if let Some(title) = &self.title {
+            inner_lines.push(format!(r"\frametitle{{{}}}", title.export()?).into());
+        }
+        if let Some(subtitle) = &self.subtitle {
+            inner_lines.push(format!(r"\framesubtitle{{{}}}", subtitle.export()?).into());
+        }
+        for part in self.parts.iter() {
+            inner_lines.extend(part.export()?.lines);
+        }
+
+        let mut lines = vec![begin.into()];
+        for mut line in inner_lines {
+            line.indentation += 1;
+            lines.push(line);
+        }
+        lines.push(r"\end{frame}".to_owned().into());
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameOption {
+    //needed for verbatim content like code listings
+    Fragile,
+    //splits content that does not fit onto several slides
+    AllowFrameBreaks,
+    //no headline, footline and sidebars
+    Plain,
+    //where the content sits on the slide
+    Alignment(VerticalAlignment),
+}
+
+impl ToLatex for FrameOption {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let option = match self {
+            FrameOption::Fragile => "fragile",
+            FrameOption::AllowFrameBreaks => "allowframebreaks",
+            FrameOption::Plain => "plain",
+            FrameOption::Alignment(alignment) => alignment.name(),
+        };
+        Ok(vec![option.to_owned()].into())
+    }
+}
+
+impl NamedOption for FrameOption {
+    fn name(&self) -> &'static str {
+        match self {
+            FrameOption::Fragile => "fragile",
+            FrameOption::AllowFrameBreaks => "allowframebreaks",
+            FrameOption::Plain => "plain",
+            FrameOption::Alignment(_) => "alignment",
+        }
+    }
+}
+
+//a titled box on a slide
+#[derive(Clone)]
+pub struct Block {
+    kind: BlockKind,
+    title: Text,
+    parts: Vec<LatexPart>,
+}
+
+impl Block {
+    pub fn new(kind: BlockKind, title: impl Into<Text>) -> Self {
+        Block {
+            kind,
+            title: title.into(),
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn plain(title: impl Into<Text>) -> Self {
+        Self::new(BlockKind::Block, title)
+    }
+
+    pub fn alert(title: impl Into<Text>) -> Self {
+        Self::new(BlockKind::Alert, title)
+    }
+
+    pub fn example(title: impl Into<Text>) -> Self {
+        Self::new(BlockKind::Example, title)
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, LatexPart> {
+        self.parts.iter()
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        vec![&self.title]
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.title.get_colors()
+    }
+}
+
+impl ToLatex for Block {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let environment = self.kind.environment();
+        let mut lines =
+            vec![format!(r"\begin{{{environment}}}{{{}}}", self.title.export()?).into()];
+        for part in self.parts.iter() {
+            let mut part_lines = part.export()?;
+            for mut part_line in part_lines.drain(..) {
+                part_line.indentation += 1;
+                lines.push(part_line);
+            }
+        }
+        lines.push(format!(r"\end{{{environment}}}").into());
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Block,
+    //highlighted as a warning
+    Alert,
+    Example,
+}
+
+impl BlockKind {
+    fn environment(&self) -> &'static str {
+        match self {
+            BlockKind::Block => "block",
+            BlockKind::Alert => "alertblock",
+            BlockKind::Example => "exampleblock",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Block, Frame, FrameOption};
+    use crate::latex::{
+        DocumentClass, Latex, LatexError, LatexPart, Text, ToLatex, VerticalAlignment,
+    };
+
+    #[test]
+    fn frames_have_titles_and_blocks() {
+        let frame = Frame::new()
+            .title("Results")
+            .subtitle(Text::new().alert("fast"))
+            .option(FrameOption::Fragile)
+            .option(FrameOption::Alignment(VerticalAlignment::Top))
+            .part(Block::alert("Note").part(Text::from("a")))
+            .part(LatexPart::Pause)
+            .part(Block::example("Example"));
+        let output = Latex::new(DocumentClass::Beamer)
+            .part(frame)
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(
+            r"	\begin{frame}[fragile, t]
+		\frametitle{Results}
+		\framesubtitle{\alert{fast}}
+		\begin{alertblock}{Note}
+			a
+		\end{alertblock}
+		\pause
+		\begin{exampleblock}{Example}
+		\end{exampleblock}
+	\end{frame}"
+        ));
+
+        let alert = Latex::new(DocumentClass::Article)
+            .part(Text::new().alert("fast"))
+            .export();
+        assert!(matches!(alert, Err(LatexError::PartNotAllowed("alert"))));
+        let conflict = Latex::new(DocumentClass::Beamer)
+            .strict_options()
+            .part(
+                Frame::new()
+                    .option(FrameOption::Alignment(VerticalAlignment::Top))
+                    .option(FrameOption::Alignment(VerticalAlignment::Bottom)),
+            )
+            .export();
+        assert!(matches!(
+            conflict,
+            Err(LatexError::ConflictingOption("alignment"))
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+use super::tikz::finite;
+use super::{LatexError, LatexLines, LatexResult, NamedOption, OptionSet, SectionLevel, ToLatex};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DocumentClass {
+    Article,
+    Report,
+    Book,
+    Letter,
+    //a single picture or formula, cropped to its content
+    Standalone,
+    //the KOMA-Script versions of article and report
+    Scrartcl,
+    Scrreprt,
+    Beamer,
+    //any other class by name, nothing about it is checked
+    Custom(String),
+}
+
+impl DocumentClass {
+    pub fn name(&self) -> &str {
+        match self {
+            DocumentClass::Article => "article",
+            DocumentClass::Report => "report",
+            DocumentClass::Book => "book",
+            DocumentClass::Letter => "letter",
+            DocumentClass::Standalone => "standalone",
+            DocumentClass::Scrartcl => "scrartcl",
+            DocumentClass::Scrreprt => "scrreprt",
+            DocumentClass::Beamer => "beamer",
+            DocumentClass::Custom(name) => name,
+        }
+    }
+
+    pub fn allows_section(&self, level: SectionLevel) -> bool {
+        match self {
+            DocumentClass::Article | DocumentClass::Scrartcl => level != SectionLevel::Chapter,
+            DocumentClass::Report
+            | DocumentClass::Book
+            | DocumentClass::Scrreprt
+            | DocumentClass::Custom(_) => true,
+            DocumentClass::Letter | DocumentClass::Standalone => false,
+            //beamer uses the sections for its navigation, paragraphs make no sense on slides
+            DocumentClass::Beamer => {
+                !matches!(level, SectionLevel::Chapter | SectionLevel::Paragraph)
+            }
+        }
+    }
+
+    pub fn allows_overlays(&self) -> bool {
+        matches!(self, DocumentClass::Beamer | DocumentClass::Custom(_))
+    }
+
+    //frames and the other beamer environments
+    pub fn allows_frames(&self) -> bool {
+        matches!(self, DocumentClass::Beamer | DocumentClass::Custom(_))
+    }
+
+    //figures, letters and standalone documents have no floats
+    pub fn allows_floats(&self) -> bool {
+        !matches!(self, DocumentClass::Letter | DocumentClass::Standalone)
+    }
+
+    //packages the class loads itself or has its own version of
+    pub fn provides_package(&self, name: &str) -> bool {
+        match self {
+            //beamer reads enumerate labels itself and always loads xcolor
+            DocumentClass::Beamer => ["enumerate", "xcolor"].contains(&name),
+            _ => false,
+        }
+    }
+
+    fn check_option(&self, option: &ClassOption) -> LatexResult<()> {
+        let allowed = match (self, option) {
+            (DocumentClass::Custom(_), _) => true,
+            (_, ClassOption::FontSize(size)) => {
+                if !self.allows_font_size(*size) {
+                    return Err(LatexError::UnsupportedFontSize(*size));
+                }
+                true
+            }
+            (DocumentClass::Beamer, ClassOption::TwoColumn | ClassOption::Landscape) => false,
+            (_, ClassOption::AspectRatio(_)) => *self == DocumentClass::Beamer,
+            (_, ClassOption::Border(_)) => *self == DocumentClass::Standalone,
+            _ => true,
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(LatexError::ClassOptionNotAllowed(option.name()))
+        }
+    }
+
+    fn allows_font_size(&self, size: u8) -> bool {
+        match self {
+            DocumentClass::Beamer => [8, 9, 10, 11, 12, 14, 17, 20].contains(&size),
+            //KOMA-Script scales to any size
+            DocumentClass::Scrartcl | DocumentClass::Scrreprt | DocumentClass::Custom(_) => {
+                size > 0
+            }
+            _ => (10..=12).contains(&size),
+        }
+    }
+
+    pub(crate) fn get_document_class_line(
+        &self,
+        options: &OptionSet<ClassOption>,
+    ) -> LatexResult<String> {
+        for option in options.iter() {
+            self.check_option(option)?;
+        }
+
+        let mut class_options = Vec::new();
+        let has_font_size = options
+            .iter()
+            .any(|option| matches!(option, ClassOption::FontSize(_)));
+        if *self == DocumentClass::Article && !has_font_size {
+            class_options.push("10pt".to_owned());
+        }
+        if !options.is_empty() {
+            class_options.push(options.export_list()?);
+        }
+
+        let name = self.name();
+        if class_options.is_empty() {
+            Ok(format!(r"\documentclass{{{name}}}"))
+        } else {
+            Ok(format!(
+                r"\documentclass[{}]{{{name}}}",
+                class_options.join(", ")
+            ))
+        }
+    }
+}
+
+//an option of `\documentclass`, article uses 10pt unless a font size is given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClassOption {
+    //base font size in pt
+    FontSize(u8),
+    Paper(Paper),
+    TwoColumn,
+    Landscape,
+    Draft,
+    //only for beamer
+    AspectRatio(AspectRatio),
+    //space around the content of a standalone document in pt
+    Border(f32),
+}
+
+impl ToLatex for ClassOption {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let option = match self {
+            ClassOption::FontSize(size) => format!("{size}pt"),
+            ClassOption::Paper(paper) => paper.name().to_owned(),
+            ClassOption::TwoColumn => "twocolumn".to_owned(),
+            ClassOption::Landscape => "landscape".to_owned(),
+            ClassOption::Draft => "draft".to_owned(),
+            ClassOption::AspectRatio(ratio) => format!("aspectratio={}", ratio.value()),
+            ClassOption::Border(border) => format!("border={}pt", finite(*border)?),
+        };
+        Ok(vec![option].into())
+    }
+}
+
+impl NamedOption for ClassOption {
+    fn name(&self) -> &'static str {
+        match self {
+            ClassOption::FontSize(_) => "font size",
+            ClassOption::Paper(_) => "paper",
+            ClassOption::TwoColumn => "twocolumn",
+            ClassOption::Landscape => "landscape",
+            ClassOption::Draft => "draft",
+            ClassOption::AspectRatio(_) => "aspectratio",
+            ClassOption::Border(_) => "border",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paper {
+    A4,
+    A5,
+    B5,
+    Letter,
+    Legal,
+    Executive,
+}
+
+impl Paper {
+    fn name(&self) -> &'static str {
+        match self {
+            Paper::A4 => "a4paper",
+            Paper::A5 => "a5paper",
+            Paper::B5 => "b5paper",
+            Paper::Letter => "letterpaper",
+            Paper::Legal => "legalpaper",
+            Paper::Executive => "executivepaper",
+        }
+    }
+}
+
+//the slide formats beamer supports, width to height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AspectRatio {
+    Ratio16x10,
+    Ratio16x9,
+    Ratio14x9,
+    Ratio5x4,
+    Ratio4x3,
+    Ratio3x2,
+}
+
+impl AspectRatio {
+    fn value(&self) -> &'static str {
+        match self {
+            AspectRatio::Ratio16x10 => "1610",
+            AspectRatio::Ratio16x9 => "169",
+            AspectRatio::Ratio14x9 => "149",
+            AspectRatio::Ratio5x4 => "54",
+            AspectRatio::Ratio4x3 => "43",
+            AspectRatio::Ratio3x2 => "32",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AspectRatio, ClassOption, DocumentClass, Paper};
+    use crate::latex::{Figure, Frame, Latex, LatexError, Section, SectionLevel, Tikz, ToLatex};
+
+    #[test]
+    fn class_options_are_checked() {
+        let class_line = |latex: Latex| {
+            let output = latex.export().unwrap().to_string();
+            output.lines().next().unwrap().to_owned()
+        };
+        assert_eq!(
+            class_line(Latex::new(DocumentClass::Article)),
+            r"\documentclass[10pt]{article}"
+        );
+        assert_eq!(
+            class_line(
+                Latex::new(DocumentClass::Report)
+                    .class_option(ClassOption::FontSize(12))
+                    .class_option(ClassOption::Paper(Paper::A4))
+                    .class_option(ClassOption::TwoColumn)
+            ),
+            r"\documentclass[12pt, a4paper, twocolumn]{report}"
+        );
+        assert_eq!(
+            class_line(
+                Latex::new(DocumentClass::Beamer)
+                    .class_option(ClassOption::AspectRatio(AspectRatio::Ratio16x9))
+            ),
+            r"\documentclass[aspectratio=169]{beamer}"
+        );
+        assert_eq!(
+            class_line(Latex::new(DocumentClass::Custom("ieeetran".to_owned()))),
+            r"\documentclass{ieeetran}"
+        );
+
+        let aspect_ratio = Latex::new(DocumentClass::Article)
+            .class_option(ClassOption::AspectRatio(AspectRatio::Ratio4x3))
+            .export();
+        assert!(matches!(
+            aspect_ratio,
+            Err(LatexError::ClassOptionNotAllowed("aspectratio"))
+        ));
+        let font_size = Latex::new(DocumentClass::Book)
+            .class_option(ClassOption::FontSize(14))
+            .export();
+        assert!(matches!(
+            font_size,
+            Err(LatexError::UnsupportedFontSize(14))
+        ));
+
+        let chapter = Section::new(SectionLevel::Chapter, "Intro");
+        assert!(Latex::new(DocumentClass::Scrreprt)
+            .part(chapter.clone())
+            .export()
+            .is_ok());
+        assert!(matches!(
+            Latex::new(DocumentClass::Letter).part(chapter).export(),
+            Err(LatexError::SectionNotAllowed(SectionLevel::Chapter))
+        ));
+        assert!(matches!(
+            Latex::new(DocumentClass::Standalone)
+                .part(Figure::new(Tikz::new()))
+                .export(),
+            Err(LatexError::PartNotAllowed("figure"))
+        ));
+        assert!(matches!(
+            Latex::new(DocumentClass::Article)
+                .part(Frame::new())
+                .export(),
+            Err(LatexError::PartNotAllowed("frame"))
+        ));
+    }
+}
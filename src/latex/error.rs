@@ -3,7 +3,7 @@ use std::io::Error as IOError;
 
 use derive_more::From;
 
-use super::TikzError;
+use super::{SectionLevel, TikzError, Width};
 
 pub type LatexResult<T> = Result<T, LatexError>;
 
@@ -13,4 +13,70 @@ pub enum LatexError {
     Tikz(TikzError),
     IO(IOError),
     PathIsNoFile,
+    EmptyPalette,
+    #[from(ignore)]
+    InvalidColor(String),
+    //transparency only works in tikz
+    AlphaNotSupported,
+    //only named pictures can be compiled on their own
+    UnnamedTikz,
+    EmptyOverlaySpec,
+    #[from(ignore)]
+    InvalidOverlaySpec(String),
+    #[from(ignore)]
+    InvalidEnumerateLabel(String),
+    OverlayNotSupported,
+    //a part the document class has no environment for
+    PartNotAllowed(&'static str),
+    #[from(ignore)]
+    TableRowWidth {
+        expected: usize,
+        found: usize,
+    },
+    #[from(ignore)]
+    InvalidPlacement(String),
+    #[from(ignore)]
+    InvalidVariable(String),
+    #[from(ignore)]
+    InvalidLabel(String),
+    LabelWithoutCaption,
+    //the subcaption environment only works inside a figure
+    SubFigureOutsideFigure,
+    //widths have to be finite and greater than 0
+    #[from(ignore)]
+    InvalidWidth(Width),
+    #[from(ignore)]
+    DuplicateLabel(String),
+    #[from(ignore)]
+    DanglingReference(String),
+    //a label on something without a number, `\ref` would print the number of something else
+    #[from(ignore)]
+    UnnumberedLabel(String),
+    #[from(ignore)]
+    SectionNotAllowed(SectionLevel),
+    #[from(ignore)]
+    SectionInFrame(SectionLevel),
+    //starred headings take no short title and stay out of the table of contents
+    #[from(ignore)]
+    ShortTitleOnStarredSection(SectionLevel),
+    #[from(ignore)]
+    InvalidSectionNesting {
+        parent: SectionLevel,
+        child: SectionLevel,
+    },
+    #[from(ignore)]
+    UnsupportedFontSize(u8),
+    #[from(ignore)]
+    ClassOptionNotAllowed(&'static str),
+    //an option of the document class or a frame set twice with different values
+    #[from(ignore)]
+    ConflictingOption(&'static str),
+    #[from(ignore)]
+    IncompatiblePackages(&'static str, &'static str),
+    //a key value option of a package that was given two different values
+    #[from(ignore)]
+    PackageOptionClash {
+        package: String,
+        option: String,
+    },
 }
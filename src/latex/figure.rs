@@ -0,0 +1,310 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::slice::Iter;
+use std::str::FromStr;
+
+use super::{Color, Label, LatexError, LatexLines, LatexPart, LatexResult, Text, ToLatex, Width};
+
+//a floating figure around a tikz picture, an image or any other part
+#[derive(Clone)]
+pub struct Figure {
+    parts: Vec<LatexPart>,
+    caption: Option<Text>,
+    short_caption: Option<Text>,
+    placement: Option<Placement>,
+    label: Option<Label>,
+}
+
+impl Figure {
+    pub fn new(content: impl Into<LatexPart>) -> Self {
+        Figure {
+            parts: vec![content.into()],
+            caption: None,
+            short_caption: None,
+            placement: None,
+            label: None,
+        }
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<Text>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    //the caption used in the list of figures
+    pub fn short_caption(mut self, short_caption: impl Into<Text>) -> Self {
+        self.short_caption = Some(short_caption.into());
+        self
+    }
+
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = Some(placement);
+        self
+    }
+
+    //the label refers to the caption, so a labeled figure needs one
+    pub fn label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn get_label(&self) -> Option<&Label> {
+        self.label.as_ref()
+    }
+
+    pub fn get_placement(&self) -> Option<Placement> {
+        self.placement
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, LatexPart> {
+        self.parts.iter()
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        self.caption
+            .iter()
+            .chain(self.short_caption.iter())
+            .collect()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.texts()
+            .into_iter()
+            .flat_map(|text| text.get_colors())
+            .collect()
+    }
+}
+
+impl ToLatex for Figure {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut begin = r"\begin{figure}".to_owned();
+        if let Some(placement) = self.placement {
+            begin.push_str(&format!("[{placement}]"));
+        }
+
+        let mut inner_lines = vec![r"\centering".to_owned().into()];
+        for part in self.parts.iter() {
+            inner_lines.extend(part.export()?.lines);
+        }
+
+        match (&self.caption, &self.short_caption) {
+            (Some(caption), None) => {
+                inner_lines.push(format!(r"\caption{{{}}}", caption.export()?).into())
+            }
+            (Some(caption), Some(short_caption)) => inner_lines.push(
+                format!(
+                    r"\caption[{}]{{{}}}",
+                    short_caption.export()?,
+                    caption.export()?
+                )
+                .into(),
+            ),
+            (None, _) if self.label.is_some() => return Err(LatexError::LabelWithoutCaption),
+            (None, _) => {}
+        }
+        if let Some(label) = &self.label {
+            inner_lines.push(label.export()?.to_string().into());
+        }
+
+        let mut lines = vec![begin.into()];
+        for mut line in inner_lines {
+            line.indentation += 1;
+            lines.push(line);
+        }
+        lines.push(r"\end{figure}".to_owned().into());
+        Ok(lines.into())
+    }
+}
+
+//where latex may put a float, `htbp` style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Placement {
+    here: bool,
+    top: bool,
+    bottom: bool,
+    page: bool,
+    //`!`, ignores some of latex's rules about good float positions
+    force: bool,
+    //`H` from the `float` package, exactly here and not floating at all
+    exact: bool,
+}
+
+impl Placement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exact() -> Self {
+        Placement {
+            exact: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn here(mut self) -> Self {
+        self.here = true;
+        self
+    }
+
+    pub fn top(mut self) -> Self {
+        self.top = true;
+        self
+    }
+
+    pub fn bottom(mut self) -> Self {
+        self.bottom = true;
+        self
+    }
+
+    pub fn page(mut self) -> Self {
+        self.page = true;
+        self
+    }
+
+    pub fn force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl Display for Placement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.exact {
+            return write!(f, "H");
+        }
+        if self.force {
+            write!(f, "!")?;
+        }
+        for (set, c) in [
+            (self.here, 'h'),
+            (self.top, 't'),
+            (self.bottom, 'b'),
+            (self.page, 'p'),
+        ] {
+            if set {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Placement {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut placement = Placement::new();
+        for c in s.chars() {
+            placement = match c {
+                'h' => placement.here(),
+                't' => placement.top(),
+                'b' => placement.bottom(),
+                'p' => placement.page(),
+                '!' => placement.force(),
+                'H' => Placement::exact(),
+                _ => return Err(LatexError::InvalidPlacement(s.to_owned())),
+            };
+        }
+        if placement == Placement::new() || (placement.exact && s.len() > 1) {
+            return Err(LatexError::InvalidPlacement(s.to_owned()));
+        }
+        Ok(placement)
+    }
+}
+
+//an image file included with `graphicx`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    path: String,
+    //as a fraction of `\linewidth`
+    width: Option<f32>,
+}
+
+impl Image {
+    pub fn new(path: impl Into<String>) -> Self {
+        Image {
+            path: path.into(),
+            width: None,
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+}
+
+impl ToLatex for Image {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let line = match self.width {
+            Some(width) => {
+                let width = Width::LineWidth(width);
+                width.check()?;
+                format!(r"\includegraphics[width={width}]{{{}}}", self.path)
+            }
+            None => format!(r"\includegraphics{{{}}}", self.path),
+        };
+        Ok(vec![line].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Figure, Image};
+    use crate::latex::{DocumentClass, Latex, LatexError, RefKind, Text, Tikz, ToLatex, Width};
+
+    #[test]
+    fn figures_are_referenced() {
+        let figure = Figure::new(Tikz::new())
+            .caption("Setup")
+            .placement("htbp".parse().unwrap())
+            .label("fig:setup");
+        let text = Text::from("See ").reference(RefKind::CRef, "fig:setup");
+        let output = Latex::new(DocumentClass::Article)
+            .part(figure.clone())
+            .part(text)
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(
+            r"\usepackage{hyperref}
+\usepackage{cleveref}"
+        ));
+        assert!(output.contains(
+            "\t\\begin{figure}[htbp]\n\t\t\\centering\n\t\t\\begin{tikzpicture}[]\n\t\t\\end{tikzpicture}\n\t\t\\caption{Setup}\n\t\t\\label{fig:setup}\n\t\\end{figure}"
+        ));
+        assert!(output.contains(r"See \cref{fig:setup}"));
+
+        let dangling = Latex::new(DocumentClass::Article)
+            .part(figure)
+            .part(Text::new().reference(RefKind::Ref, "fig:missing"))
+            .export();
+        assert!(
+            matches!(dangling, Err(LatexError::DanglingReference(label)) if label == "fig:missing")
+        );
+    }
+
+    #[test]
+    fn images_need_a_positive_width() {
+        let image = |width: f32| Image::new("plot.png").width(width).export();
+        assert_eq!(
+            image(0.5).unwrap().to_string(),
+            r"\includegraphics[width=0.5\linewidth]{plot.png}"
+        );
+        for width in [0.0, -0.5] {
+            assert!(matches!(
+                image(width),
+                Err(LatexError::InvalidWidth(Width::LineWidth(_)))
+            ));
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontSize {
+    Tiny,
+    ScriptSize,
+    FootnoteSize,
+    Small,
+    NormalSize,
+    Large,
+    //\Large
+    Larger,
+    //\LARGE
+    Largest,
+    Huge,
+    //\Huge
+    Huger,
+}
+
+impl FontSize {
+    pub fn command(&self) -> &'static str {
+        match self {
+            FontSize::Tiny => r"\tiny",
+            FontSize::ScriptSize => r"\scriptsize",
+            FontSize::FootnoteSize => r"\footnotesize",
+            FontSize::Small => r"\small",
+            FontSize::NormalSize => r"\normalsize",
+            FontSize::Large => r"\large",
+            FontSize::Larger => r"\Large",
+            FontSize::Largest => r"\LARGE",
+            FontSize::Huge => r"\huge",
+            FontSize::Huger => r"\Huge",
+        }
+    }
+}
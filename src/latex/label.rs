@@ -0,0 +1,63 @@
+use derive_more::From;
+
+use super::{LatexError, LatexLines, LatexResult, ToLatex};
+
+//the name of something that can be referenced, written as `\label{name}`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, From)]
+pub struct Label(String);
+
+impl Label {
+    pub fn new(name: impl Into<String>) -> Self {
+        Label(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    //the name as written into `\label` and `\ref`, braces, commands or comments would break them
+    pub fn checked_name(&self) -> LatexResult<&str> {
+        let invalid = |c: char| c.is_whitespace() || matches!(c, '{' | '}' | '\\' | '#' | '%');
+        if self.0.is_empty() || self.0.contains(invalid) {
+            Err(LatexError::InvalidLabel(self.0.clone()))
+        } else {
+            Ok(&self.0)
+        }
+    }
+}
+
+impl From<&str> for Label {
+    fn from(name: &str) -> Self {
+        Label(name.to_owned())
+    }
+}
+
+impl ToLatex for Label {
+    fn export(&self) -> LatexResult<LatexLines> {
+        Ok(vec![format!(r"\label{{{}}}", self.checked_name()?)].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Label;
+    use crate::latex::{LatexError, RefKind, Text, ToLatex};
+
+    #[test]
+    fn label_names_are_checked() {
+        assert_eq!(
+            Label::new("fig:setup-2").export().unwrap().to_string(),
+            r"\label{fig:setup-2}"
+        );
+        for name in ["", "fig setup", "a}b", "a{b", r"\x", "#1", "50%", "a\tb"] {
+            assert!(matches!(
+                Label::new(name).export(),
+                Err(LatexError::InvalidLabel(_))
+            ));
+            assert!(matches!(
+                Text::new().reference(RefKind::CRef, name).export(),
+                Err(LatexError::InvalidLabel(_))
+            ));
+        }
+    }
+}
@@ -1,15 +1,47 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 
 use derive_more::From;
 
-use super::{LatexLines, LatexResult, Tikz, ToLatex};
+use super::{
+    Align, Block, Color, Columns, Equation, Figure, Frame, Image, Label, LatexLines, LatexResult,
+    Lint, List, Package, Section, SubFigure, Table, Text, Tikz, ToLatex, Visible,
+};
 
 #[derive(From, Clone)]
 pub enum LatexPart {
     #[from]
     Tikz(Tikz),
-    Frame(Vec<LatexPart>),
+    #[from]
+    Frame(Frame),
     Center(Vec<LatexPart>),
+    #[from]
+    Visible(Visible<LatexPart>),
+    #[from]
+    Text(Text),
+    //text followed by an empty line, which ends the paragraph
+    Paragraph(Text),
+    #[from]
+    Section(Section),
+    #[from]
+    List(List),
+    #[from]
+    Table(Table),
+    #[from]
+    Equation(Equation),
+    #[from]
+    Align(Align),
+    #[from]
+    Figure(Figure),
+    #[from]
+    Image(Image),
+    #[from]
+    SubFigure(SubFigure),
+    #[from]
+    Columns(Columns),
+    #[from]
+    Block(Block),
+    //reveals the rest of the frame on the next slide
+    Pause,
 }
 
 pub struct FullPartIter<'p> {
@@ -31,12 +63,125 @@ impl<'p> Iterator for FullPartIter<'p> {
 }
 
 impl LatexPart {
-    pub fn iter_next_inner(
-        &self,
-    ) -> Option<impl Iterator<Item = &LatexPart> + DoubleEndedIterator> {
+    pub fn iter_next_inner(&self) -> Option<Box<dyn DoubleEndedIterator<Item = &LatexPart> + '_>> {
+        match self {
+            LatexPart::Tikz(_)
+            | LatexPart::Text(_)
+            | LatexPart::Paragraph(_)
+            | LatexPart::Table(_)
+            | LatexPart::Equation(_)
+            | LatexPart::Align(_)
+            | LatexPart::Image(_)
+            | LatexPart::Pause => None,
+            LatexPart::Center(inner) => Some(Box::new(inner.iter())),
+            LatexPart::Frame(frame) => Some(Box::new(frame.iter_inner())),
+            LatexPart::Block(block) => Some(Box::new(block.iter_inner())),
+            LatexPart::Visible(vis) => Some(Box::new(vis.iter_inner())),
+            LatexPart::Section(section) => Some(Box::new(section.iter_inner())),
+            LatexPart::List(list) => Some(Box::new(list.iter_inner())),
+            LatexPart::Figure(figure) => Some(Box::new(figure.iter_inner())),
+            LatexPart::SubFigure(sub_figure) => Some(Box::new(sub_figure.iter_inner())),
+            LatexPart::Columns(columns) => Some(Box::new(columns.iter_inner())),
+        }
+    }
+
+    //the colors used by this part itself, not by its inner parts
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        match self {
+            LatexPart::Tikz(tikz) => tikz.get_colors(),
+            LatexPart::Text(text) | LatexPart::Paragraph(text) => text.get_colors(),
+            LatexPart::Section(section) => section.get_colors(),
+            LatexPart::List(list) => list.get_colors(),
+            LatexPart::Table(table) => table.get_colors(),
+            LatexPart::Figure(figure) => figure.get_colors(),
+            LatexPart::SubFigure(sub_figure) => sub_figure.get_colors(),
+            LatexPart::Frame(frame) => frame.get_colors(),
+            LatexPart::Block(block) => block.get_colors(),
+            LatexPart::Center(_)
+            | LatexPart::Visible(_)
+            | LatexPart::Equation(_)
+            | LatexPart::Align(_)
+            | LatexPart::Image(_)
+            | LatexPart::Columns(_)
+            | LatexPart::Pause => BTreeSet::new(),
+        }
+    }
+
+    //the texts held by this part itself, not by its inner parts
+    pub fn texts(&self) -> Vec<&Text> {
+        match self {
+            LatexPart::Text(text) | LatexPart::Paragraph(text) => vec![text],
+            LatexPart::Tikz(tikz) => tikz.texts(),
+            LatexPart::Section(section) => section.texts(),
+            LatexPart::List(list) => list.texts(),
+            LatexPart::Table(table) => table.texts(),
+            LatexPart::Figure(figure) => figure.texts(),
+            LatexPart::SubFigure(sub_figure) => sub_figure.texts(),
+            LatexPart::Frame(frame) => frame.texts(),
+            LatexPart::Block(block) => block.texts(),
+            _ => Vec::new(),
+        }
+    }
+
+    //the labels defined by this part itself, not by its inner parts
+    pub fn labels(&self) -> Vec<&Label> {
+        match self {
+            LatexPart::Section(section) => section.get_label().into_iter().collect(),
+            LatexPart::Equation(equation) => equation.get_label().into_iter().collect(),
+            LatexPart::Align(align) => align.iter_labels().collect(),
+            LatexPart::Figure(figure) => figure.get_label().into_iter().collect(),
+            LatexPart::SubFigure(sub_figure) => sub_figure.get_label().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    //the labels of this part itself that have no number to point to
+    pub fn unnumbered_labels(&self) -> Vec<&Label> {
+        match self {
+            LatexPart::Section(section) => section.unnumbered_label().into_iter().collect(),
+            LatexPart::Equation(equation) => equation.unnumbered_label().into_iter().collect(),
+            LatexPart::Align(align) => align.iter_unnumbered_labels().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    //the packages needed by this part itself, not by its inner parts
+    pub fn packages(&self) -> Vec<Package> {
+        let mut packages = match self {
+            LatexPart::Tikz(tikz) => tikz.packages(),
+            LatexPart::List(list) if list.has_label() => vec![Package::new("enumerate")],
+            LatexPart::Table(table) => table.packages(),
+            LatexPart::Equation(_) | LatexPart::Align(_) => {
+                vec![Package::new("amsmath"), Package::new("amssymb")]
+            }
+            LatexPart::Figure(figure)
+                if figure
+                    .get_placement()
+                    .is_some_and(|placement| placement.is_exact()) =>
+            {
+                vec![Package::new("float")]
+            }
+            LatexPart::Image(_) => vec![Package::new("graphicx")],
+            LatexPart::SubFigure(_) => vec![Package::new("subcaption")],
+            _ => Vec::new(),
+        };
+        packages.extend(self.texts().iter().flat_map(|text| text.packages()));
+        packages
+    }
+
+    //the lints of this part itself, not its inner parts
+    pub fn lints(&self) -> Vec<Lint> {
         match self {
-            LatexPart::Tikz(_) => None,
-            LatexPart::Center(inner) | LatexPart::Frame(inner) => Some(inner.iter()),
+            LatexPart::Tikz(tikz) => tikz.lints(),
+            LatexPart::Table(table) => table.lints(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        match self {
+            LatexPart::Tikz(tikz) => tikz.tikz_libraries(),
+            _ => BTreeSet::new(),
         }
     }
 
@@ -51,16 +196,30 @@ impl ToLatex for LatexPart {
     fn export(&self) -> LatexResult<LatexLines> {
         match self {
             LatexPart::Tikz(tikz) => tikz.export(),
-            LatexPart::Frame(inner) => wrap(
-                r"\begin{frame}".to_owned(),
-                r"\end{frame}".to_owned(),
-                inner,
-            ),
+            LatexPart::Frame(frame) => frame.export(),
+            LatexPart::Block(block) => block.export(),
+            LatexPart::Pause => Ok(vec![r"\pause".to_owned()].into()),
             LatexPart::Center(inner) => wrap(
                 r"\begin{center}".to_owned(),
                 r"\end{center}".to_owned(),
                 inner,
             ),
+            LatexPart::Visible(vis) => vis.export(),
+            LatexPart::Text(text) => text.export(),
+            LatexPart::Section(section) => section.export(),
+            LatexPart::List(list) => list.export(),
+            LatexPart::Table(table) => table.export(),
+            LatexPart::Equation(equation) => equation.export(),
+            LatexPart::Align(align) => align.export(),
+            LatexPart::Figure(figure) => figure.export(),
+            LatexPart::Image(image) => image.export(),
+            LatexPart::SubFigure(sub_figure) => sub_figure.export(),
+            LatexPart::Columns(columns) => columns.export(),
+            LatexPart::Paragraph(text) => {
+                let mut lines = text.export()?;
+                lines.push(String::new().into());
+                Ok(lines)
+            }
         }
     }
 }
@@ -0,0 +1,355 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::slice::Iter;
+
+use super::tikz::finite;
+use super::{Color, Label, LatexError, LatexLines, LatexPart, LatexResult, Text, ToLatex};
+
+//a width relative to the page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Width {
+    //a fraction of `\textwidth`, the width of the text on the page
+    TextWidth(f32),
+    //a fraction of `\linewidth`, the width of the current line, e.g. inside a minipage
+    LineWidth(f32),
+}
+
+impl Width {
+    pub(super) fn check(&self) -> LatexResult<()> {
+        match self {
+            Width::TextWidth(fraction) | Width::LineWidth(fraction) => {
+                if finite(*fraction)? <= 0.0 {
+                    return Err(LatexError::InvalidWidth(*self));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Width {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Width::TextWidth(fraction) => write!(f, r"{fraction}\textwidth"),
+            Width::LineWidth(fraction) => write!(f, r"{fraction}\linewidth"),
+        }
+    }
+}
+
+//a figure inside a figure, with its own caption like `(a)`, from the `subcaption` package
+#[derive(Clone)]
+pub struct SubFigure {
+    width: Width,
+    parts: Vec<LatexPart>,
+    caption: Option<Text>,
+    label: Option<Label>,
+}
+
+impl SubFigure {
+    pub fn new(width: Width) -> Self {
+        SubFigure {
+            width,
+            parts: Vec::new(),
+            caption: None,
+            label: None,
+        }
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<Text>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn get_label(&self) -> Option<&Label> {
+        self.label.as_ref()
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, LatexPart> {
+        self.parts.iter()
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        self.caption.iter().collect()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.caption
+            .iter()
+            .flat_map(|caption| caption.get_colors())
+            .collect()
+    }
+}
+
+impl ToLatex for SubFigure {
+    fn export(&self) -> LatexResult<LatexLines> {
+        self.width.check()?;
+        let mut inner_lines = vec![r"\centering".to_owned().into()];
+        for part in self.parts.iter() {
+            inner_lines.extend(part.export()?.lines);
+        }
+        match &self.caption {
+            Some(caption) => inner_lines.push(format!(r"\caption{{{}}}", caption.export()?).into()),
+            None if self.label.is_some() => return Err(LatexError::LabelWithoutCaption),
+            None => {}
+        }
+        if let Some(label) = &self.label {
+            inner_lines.push(label.export()?.to_string().into());
+        }
+
+        let mut lines = vec![format!(r"\begin{{subfigure}}{{{}}}", self.width).into()];
+        for mut line in inner_lines {
+            line.indentation += 1;
+            lines.push(line);
+        }
+        lines.push(r"\end{subfigure}".to_owned().into());
+        Ok(lines.into())
+    }
+}
+
+//parts next to each other, as minipages or as beamer columns
+#[derive(Clone)]
+pub struct Columns {
+    kind: ColumnsKind,
+    alignment: Option<VerticalAlignment>,
+    columns: Vec<Column>,
+}
+
+impl Columns {
+    pub fn new(kind: ColumnsKind) -> Self {
+        Columns {
+            kind,
+            alignment: None,
+            columns: Vec::new(),
+        }
+    }
+
+    pub fn minipages() -> Self {
+        Self::new(ColumnsKind::Minipage)
+    }
+
+    pub fn beamer() -> Self {
+        Self::new(ColumnsKind::Beamer)
+    }
+
+    //how the columns are aligned against each other
+    pub fn alignment(mut self, alignment: VerticalAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn kind(&self) -> ColumnsKind {
+        self.kind
+    }
+
+    pub fn iter_inner(&self) -> impl DoubleEndedIterator<Item = &LatexPart> {
+        self.columns.iter().flat_map(|column| column.parts.iter())
+    }
+
+    fn export_minipages(&self) -> LatexResult<LatexLines> {
+        let mut lines = Vec::new();
+        for (index, column) in self.columns.iter().enumerate() {
+            column.width.check()?;
+            if index > 0 {
+                //spreads the space the widths leave between the minipages
+                lines.push(r"\hfill".to_owned().into());
+            }
+            let mut begin = r"\begin{minipage}".to_owned();
+            if let Some(alignment) = self.alignment {
+                begin.push_str(&format!("[{}]", alignment.name()));
+            }
+            begin.push_str(&format!("{{{}}}", column.width));
+            lines.push(begin.into());
+            for part in column.parts.iter() {
+                let mut part_lines = part.export()?;
+                for mut part_line in part_lines.drain(..) {
+                    part_line.indentation += 1;
+                    lines.push(part_line);
+                }
+            }
+            //the `%` keeps the line break from adding a space between the minipages
+            lines.push(r"\end{minipage}%".to_owned().into());
+        }
+        Ok(lines.into())
+    }
+
+    fn export_beamer(&self) -> LatexResult<LatexLines> {
+        let mut begin = r"\begin{columns}".to_owned();
+        if let Some(alignment) = self.alignment {
+            begin.push_str(&format!("[{}]", alignment.name()));
+        }
+
+        let mut lines = vec![begin.into()];
+        for column in self.columns.iter() {
+            column.width.check()?;
+            let mut column_lines = vec![format!(r"\begin{{column}}{{{}}}", column.width).into()];
+            for part in column.parts.iter() {
+                let mut part_lines = part.export()?;
+                for mut part_line in part_lines.drain(..) {
+                    part_line.indentation += 1;
+                    column_lines.push(part_line);
+                }
+            }
+            column_lines.push(r"\end{column}".to_owned().into());
+            for mut line in column_lines {
+                line.indentation += 1;
+                lines.push(line);
+            }
+        }
+        lines.push(r"\end{columns}".to_owned().into());
+        Ok(lines.into())
+    }
+}
+
+impl ToLatex for Columns {
+    fn export(&self) -> LatexResult<LatexLines> {
+        match self.kind {
+            ColumnsKind::Minipage => self.export_minipages(),
+            ColumnsKind::Beamer => self.export_beamer(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnsKind {
+    Minipage,
+    //`columns` and `column`, only available in beamer
+    Beamer,
+}
+
+#[derive(Clone)]
+pub struct Column {
+    width: Width,
+    parts: Vec<LatexPart>,
+}
+
+impl Column {
+    pub fn new(width: Width) -> Self {
+        Column {
+            width,
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlignment {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl VerticalAlignment {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VerticalAlignment::Top => "t",
+            VerticalAlignment::Center => "c",
+            VerticalAlignment::Bottom => "b",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Column, Columns, SubFigure, VerticalAlignment, Width};
+    use crate::latex::{
+        DocumentClass, Figure, Frame, Latex, LatexError, RefKind, Text, Tikz, ToLatex,
+    };
+
+    #[test]
+    fn layouts_place_parts_side_by_side() {
+        let before = SubFigure::new(Width::LineWidth(0.45))
+            .part(Tikz::new())
+            .caption("Before")
+            .label("fig:before");
+        let after = SubFigure::new(Width::LineWidth(0.45))
+            .part(Tikz::new())
+            .caption("After");
+        let figure = Figure::new(before).part(after).caption("Comparison");
+        let output = Latex::new(DocumentClass::Article)
+            .part(figure)
+            .part(Text::new().reference(RefKind::Ref, "fig:before"))
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(r"\usepackage{subcaption}"));
+        assert!(output.contains(
+            "\t\t\\begin{subfigure}{0.45\\linewidth}\n\t\t\t\\centering\n\t\t\t\\begin{tikzpicture}[]\n\t\t\t\\end{tikzpicture}\n\t\t\t\\caption{Before}\n\t\t\t\\label{fig:before}\n\t\t\\end{subfigure}"
+        ));
+
+        let columns = Columns::minipages()
+            .alignment(VerticalAlignment::Top)
+            .column(Column::new(Width::TextWidth(0.5)).part(Text::from("left")))
+            .column(Column::new(Width::TextWidth(0.4)).part(Text::from("right")));
+        let output = Latex::new(DocumentClass::Article)
+            .part(columns.clone())
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(
+            "\t\\begin{minipage}[t]{0.5\\textwidth}\n\t\tleft\n\t\\end{minipage}%\n\t\\hfill\n\t\\begin{minipage}[t]{0.4\\textwidth}"
+        ));
+
+        let beamer_columns = Columns::beamer().column(Column::new(Width::TextWidth(0.5)));
+        let output = Latex::new(DocumentClass::Beamer)
+            .part(Frame::new().part(beamer_columns.clone()))
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(
+            "\t\t\\begin{columns}\n\t\t\t\\begin{column}{0.5\\textwidth}\n\t\t\t\\end{column}\n\t\t\\end{columns}"
+        ));
+        let not_beamer = Latex::new(DocumentClass::Article)
+            .part(beamer_columns)
+            .export();
+        assert!(matches!(
+            not_beamer,
+            Err(LatexError::PartNotAllowed("beamer columns"))
+        ));
+    }
+
+    #[test]
+    fn sub_figures_need_a_figure() {
+        let sub_figure = || SubFigure::new(Width::LineWidth(0.5)).caption("left");
+        let alone = Latex::new(DocumentClass::Article).part(sub_figure());
+        assert!(matches!(
+            alone.export(),
+            Err(LatexError::SubFigureOutsideFigure)
+        ));
+
+        let columns =
+            Columns::minipages().column(Column::new(Width::TextWidth(0.5)).part(sub_figure()));
+        let in_figure = Latex::new(DocumentClass::Article).part(Figure::new(columns));
+        assert!(in_figure.export().is_ok());
+    }
+
+    #[test]
+    fn widths_are_positive() {
+        for fraction in [0.0, -0.5] {
+            let sub_figure = SubFigure::new(Width::TextWidth(fraction));
+            assert!(matches!(
+                sub_figure.export(),
+                Err(LatexError::InvalidWidth(Width::TextWidth(_)))
+            ));
+        }
+    }
+}
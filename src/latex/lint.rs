@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use super::{Color, Paint};
+
+//the WCAG AA level for normal text, below it text is hard to read
+pub const MIN_CONTRAST_RATIO: f32 = 4.5;
+
+//a problem that does not stop the export, but should be looked at before publishing
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lint {
+    LowContrast {
+        //where the colors are used, e.g. `node "a"`
+        location: String,
+        text: Color,
+        background: Color,
+        ratio: f32,
+    },
+}
+
+impl Lint {
+    //a lint if the text is not readable on the background, transparency is ignored
+    pub(crate) fn contrast(
+        location: impl Into<String>,
+        text: &Paint,
+        background: &Paint,
+    ) -> Option<Lint> {
+        let (text, background) = (text.resolve(), background.resolve());
+        let ratio = text.contrast_ratio(background);
+        if ratio >= MIN_CONTRAST_RATIO {
+            return None;
+        }
+        Some(Lint::LowContrast {
+            location: location.into(),
+            text,
+            background,
+            ratio,
+        })
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Lint::LowContrast {
+                location,
+                text,
+                background,
+                ratio,
+            } => write!(
+                f,
+                "{location}: text {} on {} has a contrast of {ratio:.2}:1, at least {MIN_CONTRAST_RATIO}:1 is needed",
+                text.name(),
+                background.name()
+            ),
+        }
+    }
+}
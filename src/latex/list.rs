@@ -0,0 +1,374 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use super::{
+    escape, Color, LatexError, LatexLine, LatexLines, LatexPart, LatexResult, OverlaySpec, Text,
+    ToLatex,
+};
+
+#[derive(Clone)]
+pub struct List {
+    kind: ListKind,
+    //only used by enumerations
+    label: Option<EnumerateLabel>,
+    start: Option<u32>,
+    items: Vec<Item>,
+}
+
+impl List {
+    pub fn new(kind: ListKind) -> Self {
+        List {
+            kind,
+            label: None,
+            start: None,
+            items: Vec::new(),
+        }
+    }
+
+    pub fn itemize() -> Self {
+        Self::new(ListKind::Itemize)
+    }
+
+    pub fn enumerate() -> Self {
+        Self::new(ListKind::Enumerate)
+    }
+
+    pub fn description() -> Self {
+        Self::new(ListKind::Description)
+    }
+
+    //the label format of an enumeration like `(a)` or `i.`
+    pub fn label(mut self, label: EnumerateLabel) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    //the number of the first item of an enumeration
+    pub fn start(mut self, start: u32) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn item(mut self, item: impl Into<Item>) -> Self {
+        self.items.push(item.into());
+        self
+    }
+
+    pub fn kind(&self) -> ListKind {
+        self.kind
+    }
+
+    pub fn has_label(&self) -> bool {
+        self.kind == ListKind::Enumerate && self.label.is_some()
+    }
+
+    pub fn iter_items(&self) -> impl DoubleEndedIterator<Item = &Item> {
+        self.items.iter()
+    }
+
+    pub fn iter_inner(&self) -> impl DoubleEndedIterator<Item = &LatexPart> {
+        self.items.iter().flat_map(|item| item.parts.iter())
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.items
+            .iter()
+            .filter_map(|item| item.label.as_ref())
+            .flat_map(|label| label.get_colors())
+            .collect()
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        self.items
+            .iter()
+            .filter_map(|item| item.label.as_ref())
+            .collect()
+    }
+}
+
+impl ToLatex for List {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let name = self.kind.name();
+        let mut lines = Vec::new();
+
+        let mut begin = format!(r"\begin{{{name}}}");
+        if let (ListKind::Enumerate, Some(label)) = (self.kind, &self.label) {
+            begin.push_str(&format!("[{label}]"));
+        }
+        lines.push(begin.into());
+
+        if let (ListKind::Enumerate, Some(start)) = (self.kind, self.start) {
+            //the counter of the current nesting depth, so this works in nested enumerations too
+            let line = format!(
+                r"\setcounter{{\csname @enumctr\endcsname}}{{{}}}",
+                start.saturating_sub(1)
+            );
+            let mut line: LatexLine = line.into();
+            line.indentation += 1;
+            lines.push(line);
+        }
+
+        for item in self.items.iter() {
+            let mut item_lines = item.export()?;
+            for mut item_line in item_lines.drain(..) {
+                item_line.indentation += 1;
+                lines.push(item_line);
+            }
+        }
+
+        lines.push(format!(r"\end{{{name}}}").into());
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    Itemize,
+    Enumerate,
+    Description,
+}
+
+impl ListKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ListKind::Itemize => "itemize",
+            ListKind::Enumerate => "enumerate",
+            ListKind::Description => "description",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Item {
+    //the term of a description list item
+    label: Option<Text>,
+    //beamer slides the item is revealed on
+    overlay: Option<OverlaySpec>,
+    parts: Vec<LatexPart>,
+}
+
+impl Item {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn label(mut self, label: impl Into<Text>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn overlay(mut self, overlay: OverlaySpec) -> Self {
+        self.overlay = Some(overlay);
+        self
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn has_overlay(&self) -> bool {
+        self.overlay.is_some()
+    }
+}
+
+impl From<Text> for Item {
+    fn from(text: Text) -> Self {
+        Item::new().part(text)
+    }
+}
+
+impl From<&str> for Item {
+    fn from(text: &str) -> Self {
+        Item::new().part(Text::from(text))
+    }
+}
+
+impl ToLatex for Item {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut first_line = r"\item".to_owned();
+        if let Some(overlay) = &self.overlay {
+            first_line.push_str(&format!("<{}>", overlay.export()?));
+        }
+        if let Some(label) = &self.label {
+            first_line.push_str(&format!("[{}]", label.export()?));
+        }
+
+        let mut lines = vec![first_line.into()];
+        for part in self.parts.iter() {
+            let mut part_lines = part.export()?;
+            for mut part_line in part_lines.drain(..) {
+                part_line.indentation += 1;
+                lines.push(part_line);
+            }
+        }
+        Ok(lines.into())
+    }
+}
+
+//the label of an enumeration, a counter style with text around it like `(a)` or `i.`
+//exported in the syntax of the `enumerate` package, which beamer understands natively
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumerateLabel {
+    numbering: Numbering,
+    prefix: String,
+    suffix: String,
+}
+
+impl EnumerateLabel {
+    pub fn new(numbering: Numbering) -> Self {
+        EnumerateLabel {
+            numbering,
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+}
+
+//letters the enumerate package would read as a counter have to be hidden in braces
+fn protect(text: &str) -> String {
+    let escaped = escape(text);
+    if escaped
+        .chars()
+        .any(|c| matches!(c, '1' | 'a' | 'A' | 'i' | 'I' | '[' | ']'))
+    {
+        format!("{{{escaped}}}")
+    } else {
+        escaped
+    }
+}
+
+impl Display for EnumerateLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}{}{}",
+            protect(&self.prefix),
+            self.numbering.template(),
+            protect(&self.suffix)
+        )
+    }
+}
+
+impl FromStr for EnumerateLabel {
+    type Err = LatexError;
+
+    //the first counter character standing on its own decides the numbering,
+    //`(a)` is lowercase letters in parentheses, the `a` in `Task 1.` is just text
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        let alone = |position: usize| {
+            let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric());
+            !is_word(position.checked_sub(1).and_then(|before| chars.get(before)))
+                && !is_word(chars.get(position + 1))
+        };
+        let (index, numbering) = s
+            .char_indices()
+            .enumerate()
+            .filter(|(position, _)| alone(*position))
+            .find_map(|(_, (index, c))| {
+                Numbering::from_template(c).map(|numbering| (index, numbering))
+            })
+            .ok_or_else(|| LatexError::InvalidEnumerateLabel(s.to_owned()))?;
+        Ok(EnumerateLabel::new(numbering)
+            .prefix(&s[..index])
+            .suffix(&s[index + 1..]))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Numbering {
+    Arabic,
+    LowerAlpha,
+    UpperAlpha,
+    LowerRoman,
+    UpperRoman,
+}
+
+impl Numbering {
+    fn template(&self) -> char {
+        match self {
+            Numbering::Arabic => '1',
+            Numbering::LowerAlpha => 'a',
+            Numbering::UpperAlpha => 'A',
+            Numbering::LowerRoman => 'i',
+            Numbering::UpperRoman => 'I',
+        }
+    }
+
+    fn from_template(c: char) -> Option<Self> {
+        match c {
+            '1' => Some(Numbering::Arabic),
+            'a' => Some(Numbering::LowerAlpha),
+            'A' => Some(Numbering::UpperAlpha),
+            'i' => Some(Numbering::LowerRoman),
+            'I' => Some(Numbering::UpperRoman),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnumerateLabel, Item, List, Numbering};
+    use crate::latex::{DocumentClass, Latex, LatexError, OverlaySpec, ToLatex};
+
+    #[test]
+    fn lists_nest_and_reveal_items() {
+        let label: EnumerateLabel = "(a)".parse().unwrap();
+        let list = List::enumerate().label(label).start(3).item("first").item(
+            Item::new()
+                .overlay(OverlaySpec::new().onwards(2))
+                .part(List::itemize().item("nested")),
+        );
+        let expected = r"\begin{enumerate}[(a)]
+	\setcounter{\csname @enumctr\endcsname}{2}
+	\item
+		first
+	\item<2->
+		\begin{itemize}
+			\item
+				nested
+		\end{itemize}
+\end{enumerate}";
+        assert_eq!(list.export().unwrap().to_string(), expected);
+
+        let article = Latex::new(DocumentClass::Article).part(list).export();
+        assert!(matches!(article, Err(LatexError::OverlayNotSupported)));
+    }
+
+    #[test]
+    fn counters_stand_on_their_own() {
+        let label: EnumerateLabel = "Task 1.".parse().unwrap();
+        assert_eq!(
+            label,
+            EnumerateLabel::new(Numbering::Arabic)
+                .prefix("Task ")
+                .suffix(".")
+        );
+        assert_eq!(label.to_string(), "{Task }1.");
+
+        let label: EnumerateLabel = "(i)".parse().unwrap();
+        assert_eq!(
+            label,
+            EnumerateLabel::new(Numbering::LowerRoman)
+                .prefix("(")
+                .suffix(")")
+        );
+        assert!(matches!(
+            "Item".parse::<EnumerateLabel>(),
+            Err(LatexError::InvalidEnumerateLabel(_))
+        ));
+    }
+}
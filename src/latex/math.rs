@@ -0,0 +1,639 @@
+use std::fmt::Write;
+
+use super::tikz::finite;
+use super::{Label, LatexError, LatexLine, LatexLines, LatexResult, ToLatex};
+
+//a math expression, rendered without the surrounding `$` or environment
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathExpr {
+    //trusted latex math that is written out as is
+    Raw(String),
+    //letters, digits and primes only, anything else has to be built from the other variants
+    Var(String),
+    Number(f64),
+    Greek(Greek),
+    //the expressions one after another
+    Group(Vec<MathExpr>),
+    Parens(Box<MathExpr>),
+    Frac(Box<MathExpr>, Box<MathExpr>),
+    Sub(Box<MathExpr>, Box<MathExpr>),
+    Sup(Box<MathExpr>, Box<MathExpr>),
+    Sum {
+        from: Option<Box<MathExpr>>,
+        to: Option<Box<MathExpr>>,
+        body: Box<MathExpr>,
+    },
+    Integral {
+        from: Option<Box<MathExpr>>,
+        to: Option<Box<MathExpr>>,
+        body: Box<MathExpr>,
+        variable: Box<MathExpr>,
+    },
+    Matrix(MatrixKind, Vec<Vec<MathExpr>>),
+    //pairs of a value and the condition it applies under
+    Cases(Vec<(MathExpr, MathExpr)>),
+}
+
+impl MathExpr {
+    pub fn raw(latex: impl Into<String>) -> Self {
+        MathExpr::Raw(latex.into())
+    }
+
+    pub fn var(name: impl Into<String>) -> Self {
+        MathExpr::Var(name.into())
+    }
+
+    pub fn frac(numerator: impl Into<MathExpr>, denominator: impl Into<MathExpr>) -> Self {
+        MathExpr::Frac(Box::new(numerator.into()), Box::new(denominator.into()))
+    }
+
+    pub fn parens(inner: impl Into<MathExpr>) -> Self {
+        MathExpr::Parens(Box::new(inner.into()))
+    }
+
+    pub fn sum(from: Option<MathExpr>, to: Option<MathExpr>, body: impl Into<MathExpr>) -> Self {
+        MathExpr::Sum {
+            from: from.map(Box::new),
+            to: to.map(Box::new),
+            body: Box::new(body.into()),
+        }
+    }
+
+    pub fn integral(
+        from: Option<MathExpr>,
+        to: Option<MathExpr>,
+        body: impl Into<MathExpr>,
+        variable: impl Into<MathExpr>,
+    ) -> Self {
+        MathExpr::Integral {
+            from: from.map(Box::new),
+            to: to.map(Box::new),
+            body: Box::new(body.into()),
+            variable: Box::new(variable.into()),
+        }
+    }
+
+    pub fn matrix(kind: MatrixKind, rows: Vec<Vec<MathExpr>>) -> Self {
+        MathExpr::Matrix(kind, rows)
+    }
+
+    pub fn cases(cases: Vec<(MathExpr, MathExpr)>) -> Self {
+        MathExpr::Cases(cases)
+    }
+
+    //a subscript, not a subtraction
+    #[allow(clippy::should_implement_trait)]
+    pub fn sub(self, index: impl Into<MathExpr>) -> Self {
+        MathExpr::Sub(Box::new(self), Box::new(index.into()))
+    }
+
+    pub fn sup(self, exponent: impl Into<MathExpr>) -> Self {
+        MathExpr::Sup(Box::new(self), Box::new(exponent.into()))
+    }
+
+    //appends `other` to this expression
+    pub fn then(self, other: impl Into<MathExpr>) -> Self {
+        match self {
+            MathExpr::Group(mut group) => {
+                group.push(other.into());
+                MathExpr::Group(group)
+            }
+            first => MathExpr::Group(vec![first, other.into()]),
+        }
+    }
+
+    pub fn plus(self, other: impl Into<MathExpr>) -> Self {
+        self.then(MathExpr::raw(" + ")).then(other)
+    }
+
+    pub fn minus(self, other: impl Into<MathExpr>) -> Self {
+        self.then(MathExpr::raw(" - ")).then(other)
+    }
+
+    pub fn times(self, other: impl Into<MathExpr>) -> Self {
+        self.then(MathExpr::raw(r" \cdot ")).then(other)
+    }
+
+    pub fn equals(self, other: impl Into<MathExpr>) -> Self {
+        self.then(MathExpr::raw(" = ")).then(other)
+    }
+
+    pub fn render(&self) -> LatexResult<String> {
+        let mut latex = String::new();
+        match self {
+            MathExpr::Raw(raw) => latex.push_str(raw),
+            MathExpr::Var(name) => {
+                let valid = |c: char| c.is_ascii_alphanumeric() || c == '\'';
+                if name.is_empty() || !name.chars().all(valid) {
+                    return Err(LatexError::InvalidVariable(name.clone()));
+                }
+                latex.push_str(name)
+            }
+            MathExpr::Number(number) => write!(&mut latex, "{}", finite(*number)?)?,
+            MathExpr::Greek(letter) => latex.push_str(letter.command()),
+            MathExpr::Group(group) => {
+                for expr in group {
+                    let rendered = expr.render()?;
+                    //`\alpha x` must not become the unknown command `\alphax`
+                    if ends_with_command(&latex)
+                        && rendered.starts_with(|c: char| c.is_ascii_alphabetic())
+                    {
+                        latex.push(' ');
+                    }
+                    latex.push_str(&rendered);
+                }
+            }
+            MathExpr::Parens(inner) => write!(&mut latex, r"\left({}\right)", inner.render()?)?,
+            MathExpr::Frac(numerator, denominator) => write!(
+                &mut latex,
+                r"\frac{{{}}}{{{}}}",
+                numerator.render()?,
+                denominator.render()?
+            )?,
+            MathExpr::Sub(base, index) => write!(
+                &mut latex,
+                "{}_{{{}}}",
+                base.render_base(false)?,
+                index.render()?
+            )?,
+            MathExpr::Sup(base, exponent) => write!(
+                &mut latex,
+                "{}^{{{}}}",
+                base.render_base(true)?,
+                exponent.render()?
+            )?,
+            MathExpr::Sum { from, to, body } => {
+                latex.push_str(r"\sum");
+                render_limits(&mut latex, from, to)?;
+                write!(&mut latex, " {}", body.render()?)?;
+            }
+            MathExpr::Integral {
+                from,
+                to,
+                body,
+                variable,
+            } => {
+                latex.push_str(r"\int");
+                render_limits(&mut latex, from, to)?;
+                write!(&mut latex, " {} \\,d{}", body.render()?, variable.render()?)?;
+            }
+            MathExpr::Matrix(kind, rows) => {
+                let name = kind.name();
+                write!(&mut latex, r"\begin{{{name}}} ")?;
+                latex.push_str(&render_rows(rows.iter().map(|row| row.as_slice()))?);
+                write!(&mut latex, r" \end{{{name}}}")?;
+            }
+            MathExpr::Cases(cases) => {
+                let rows: Vec<[MathExpr; 2]> = cases
+                    .iter()
+                    .map(|(value, condition)| [value.clone(), condition.clone()])
+                    .collect();
+                latex.push_str(r"\begin{cases} ");
+                latex.push_str(&render_rows(rows.iter().map(|row| row.as_slice()))?);
+                latex.push_str(r" \end{cases}");
+            }
+        }
+        Ok(latex)
+    }
+
+    //a base of a sub or superscript has to be a single group,
+    //and must not end in a script of the same kind, `x^{2}^{3}` is a double superscript.
+    //sums, integrals and signed numbers would only pass their last part on, `-1^{2}` is -(1^2)
+    fn render_base(&self, superscript: bool) -> LatexResult<String> {
+        match self {
+            MathExpr::Group(_)
+            | MathExpr::Frac(_, _)
+            | MathExpr::Sum { .. }
+            | MathExpr::Integral { .. } => Ok(format!("{{{}}}", self.render()?)),
+            MathExpr::Number(number) if number.is_sign_negative() => {
+                Ok(format!("{{{}}}", self.render()?))
+            }
+            _ if self.has_script(superscript) => Ok(format!("{{{}}}", self.render()?)),
+            _ => self.render(),
+        }
+    }
+
+    //whether the unbraced chain of scripts this expression ends in has a super or subscript
+    fn has_script(&self, superscript: bool) -> bool {
+        match self {
+            MathExpr::Sup(base, _) => superscript || base.has_script(superscript),
+            MathExpr::Sub(base, _) => !superscript || base.has_script(superscript),
+            _ => false,
+        }
+    }
+}
+
+fn ends_with_command(latex: &str) -> bool {
+    match latex.rfind('\\') {
+        Some(start) => {
+            let name = &latex[start + 1..];
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    }
+}
+
+fn render_limits(
+    latex: &mut String,
+    from: &Option<Box<MathExpr>>,
+    to: &Option<Box<MathExpr>>,
+) -> LatexResult<()> {
+    if let Some(from) = from {
+        write!(latex, "_{{{}}}", from.render()?)?;
+    }
+    if let Some(to) = to {
+        write!(latex, "^{{{}}}", to.render()?)?;
+    }
+    Ok(())
+}
+
+fn render_rows<'r>(rows: impl Iterator<Item = &'r [MathExpr]>) -> LatexResult<String> {
+    let mut rendered = Vec::new();
+    for row in rows {
+        let mut cells = Vec::with_capacity(row.len());
+        for cell in row {
+            cells.push(cell.render()?);
+        }
+        rendered.push(cells.join(" & "));
+    }
+    Ok(rendered.join(r" \\ "))
+}
+
+impl From<&str> for MathExpr {
+    fn from(name: &str) -> Self {
+        MathExpr::var(name)
+    }
+}
+
+impl From<f64> for MathExpr {
+    fn from(number: f64) -> Self {
+        MathExpr::Number(number)
+    }
+}
+
+impl From<i32> for MathExpr {
+    fn from(number: i32) -> Self {
+        MathExpr::Number(number as f64)
+    }
+}
+
+impl From<Greek> for MathExpr {
+    fn from(letter: Greek) -> Self {
+        MathExpr::Greek(letter)
+    }
+}
+
+impl ToLatex for MathExpr {
+    fn export(&self) -> LatexResult<LatexLines> {
+        Ok(vec![self.render()?].into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixKind {
+    Plain,
+    //( )
+    Parens,
+    //[ ]
+    Brackets,
+    //| |
+    Determinant,
+}
+
+impl MatrixKind {
+    fn name(&self) -> &'static str {
+        match self {
+            MatrixKind::Plain => "matrix",
+            MatrixKind::Parens => "pmatrix",
+            MatrixKind::Brackets => "bmatrix",
+            MatrixKind::Determinant => "vmatrix",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Greek {
+    Alpha,
+    Beta,
+    Gamma,
+    Delta,
+    Epsilon,
+    Zeta,
+    Eta,
+    Theta,
+    Iota,
+    Kappa,
+    Lambda,
+    Mu,
+    Nu,
+    Xi,
+    Pi,
+    Rho,
+    Sigma,
+    Tau,
+    Upsilon,
+    Phi,
+    Chi,
+    Psi,
+    Omega,
+    UpperGamma,
+    UpperDelta,
+    UpperTheta,
+    UpperLambda,
+    UpperXi,
+    UpperPi,
+    UpperSigma,
+    UpperPhi,
+    UpperPsi,
+    UpperOmega,
+}
+
+impl Greek {
+    fn command(&self) -> &'static str {
+        match self {
+            Greek::Alpha => r"\alpha",
+            Greek::Beta => r"\beta",
+            Greek::Gamma => r"\gamma",
+            Greek::Delta => r"\delta",
+            Greek::Epsilon => r"\epsilon",
+            Greek::Zeta => r"\zeta",
+            Greek::Eta => r"\eta",
+            Greek::Theta => r"\theta",
+            Greek::Iota => r"\iota",
+            Greek::Kappa => r"\kappa",
+            Greek::Lambda => r"\lambda",
+            Greek::Mu => r"\mu",
+            Greek::Nu => r"\nu",
+            Greek::Xi => r"\xi",
+            Greek::Pi => r"\pi",
+            Greek::Rho => r"\rho",
+            Greek::Sigma => r"\sigma",
+            Greek::Tau => r"\tau",
+            Greek::Upsilon => r"\upsilon",
+            Greek::Phi => r"\phi",
+            Greek::Chi => r"\chi",
+            Greek::Psi => r"\psi",
+            Greek::Omega => r"\omega",
+            Greek::UpperGamma => r"\Gamma",
+            Greek::UpperDelta => r"\Delta",
+            Greek::UpperTheta => r"\Theta",
+            Greek::UpperLambda => r"\Lambda",
+            Greek::UpperXi => r"\Xi",
+            Greek::UpperPi => r"\Pi",
+            Greek::UpperSigma => r"\Sigma",
+            Greek::UpperPhi => r"\Phi",
+            Greek::UpperPsi => r"\Psi",
+            Greek::UpperOmega => r"\Omega",
+        }
+    }
+}
+
+//a displayed equation, numbered unless `unnumbered` is set
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equation {
+    expr: MathExpr,
+    numbered: bool,
+    label: Option<Label>,
+}
+
+impl Equation {
+    pub fn new(expr: impl Into<MathExpr>) -> Self {
+        Equation {
+            expr: expr.into(),
+            numbered: true,
+            label: None,
+        }
+    }
+
+    pub fn unnumbered(mut self) -> Self {
+        self.numbered = false;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn get_label(&self) -> Option<&Label> {
+        self.label.as_ref()
+    }
+
+    //a label without a number to point to
+    pub fn unnumbered_label(&self) -> Option<&Label> {
+        self.label.as_ref().filter(|_| !self.numbered)
+    }
+}
+
+impl ToLatex for Equation {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let name = if self.numbered {
+            "equation"
+        } else {
+            "equation*"
+        };
+        let mut lines = Vec::new();
+        lines.push(format!(r"\begin{{{name}}}").into());
+        let mut line: LatexLine = self.expr.render()?.into();
+        if let Some(label) = &self.label {
+            line.line_content.push_str(&label.export()?.to_string());
+        }
+        line.indentation += 1;
+        lines.push(line);
+        lines.push(format!(r"\end{{{name}}}").into());
+        Ok(lines.into())
+    }
+}
+
+//equations aligned at the `&` between the cells of each row
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Align {
+    rows: Vec<AlignRow>,
+    unnumbered: bool,
+}
+
+impl Align {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn unnumbered(mut self) -> Self {
+        self.unnumbered = true;
+        self
+    }
+
+    pub fn row(mut self, row: impl Into<AlignRow>) -> Self {
+        self.rows.push(row.into());
+        self
+    }
+
+    pub fn iter_labels(&self) -> impl Iterator<Item = &Label> {
+        self.rows.iter().filter_map(|row| row.label.as_ref())
+    }
+
+    //labels on rows without a number to point to
+    pub fn iter_unnumbered_labels(&self) -> impl Iterator<Item = &Label> {
+        self.rows
+            .iter()
+            .filter(|row| self.unnumbered || !row.numbered)
+            .filter_map(|row| row.label.as_ref())
+    }
+}
+
+impl ToLatex for Align {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let name = if self.unnumbered { "align*" } else { "align" };
+        let mut lines = Vec::new();
+        lines.push(format!(r"\begin{{{name}}}").into());
+        for (index, row) in self.rows.iter().enumerate() {
+            let mut line: LatexLine = row.export()?.to_string().into();
+            if index + 1 < self.rows.len() {
+                line.line_content.push_str(r" \\");
+            }
+            line.indentation += 1;
+            lines.push(line);
+        }
+        lines.push(format!(r"\end{{{name}}}").into());
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignRow {
+    cells: Vec<MathExpr>,
+    numbered: bool,
+    label: Option<Label>,
+}
+
+impl AlignRow {
+    pub fn new(cells: Vec<MathExpr>) -> Self {
+        AlignRow {
+            cells,
+            numbered: true,
+            label: None,
+        }
+    }
+
+    //leaves this row out of the numbering of a numbered align
+    pub fn unnumbered(mut self) -> Self {
+        self.numbered = false;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl From<Vec<MathExpr>> for AlignRow {
+    fn from(cells: Vec<MathExpr>) -> Self {
+        AlignRow::new(cells)
+    }
+}
+
+impl ToLatex for AlignRow {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut row = render_rows(std::iter::once(self.cells.as_slice()))?;
+        if !self.numbered {
+            row.push_str(r" \notag");
+        }
+        if let Some(label) = &self.label {
+            row.push_str(&label.export()?.to_string());
+        }
+        Ok(vec![row].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Align, AlignRow, Equation, Greek, MathExpr};
+    use crate::latex::{DocumentClass, Latex, LatexError, Text, ToLatex};
+
+    #[test]
+    fn math_renders() {
+        let sum = MathExpr::sum(
+            Some(MathExpr::var("i").equals(1)),
+            Some(MathExpr::var("n")),
+            MathExpr::var("x").sub("i").sup(2),
+        );
+        let equation = Equation::new(
+            MathExpr::from(Greek::Sigma)
+                .equals(MathExpr::frac(sum, "n"))
+                .equals(MathExpr::cases(vec![
+                    (MathExpr::from(0), MathExpr::raw(r"n = 0")),
+                    (MathExpr::var("s"), MathExpr::raw(r"\text{else}")),
+                ])),
+        )
+        .label("eq:variance");
+        let expected = r"\begin{equation}
+	\sigma = \frac{\sum_{i = 1}^{n} x_{i}^{2}}{n} = \begin{cases} 0 & n = 0 \\ s & \text{else} \end{cases}\label{eq:variance}
+\end{equation}";
+        assert_eq!(equation.export().unwrap().to_string(), expected);
+
+        let output = Latex::new(DocumentClass::Article)
+            .part(Text::from("with ").math(MathExpr::var("x").sup(2)))
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(r"\usepackage{amsmath}"));
+        assert!(output.contains("with $x^{2}$"));
+    }
+
+    #[test]
+    fn scripts_of_the_same_kind_are_braced() {
+        let render = |expr: MathExpr| expr.render().unwrap();
+        assert_eq!(render(MathExpr::var("x").sub("i").sup(2)), "x_{i}^{2}");
+        assert_eq!(render(MathExpr::var("x").sup(2).sup(3)), "{x^{2}}^{3}");
+        assert_eq!(render(MathExpr::var("x").sub("i").sub("j")), "{x_{i}}_{j}");
+        assert_eq!(
+            render(MathExpr::var("x").sup(2).sub("i").sup(3)),
+            "{x^{2}_{i}}^{3}"
+        );
+    }
+
+    #[test]
+    fn operators_and_signed_numbers_are_braced_as_bases() {
+        let render = |expr: MathExpr| expr.render().unwrap();
+        let sum = MathExpr::sum(Some(MathExpr::var("i")), None, MathExpr::var("x"));
+        assert_eq!(render(sum.sup(2)), r"{\sum_{i} x}^{2}");
+        let integral = MathExpr::integral(None, None, MathExpr::var("f"), MathExpr::var("x"));
+        assert_eq!(render(integral.sub(0)), r"{\int f \,dx}_{0}");
+        assert_eq!(render(MathExpr::from(-1).sup(2)), "{-1}^{2}");
+        assert_eq!(render(MathExpr::from(1.5).sup(2)), "1.5^{2}");
+    }
+
+    #[test]
+    fn variables_are_checked() {
+        assert_eq!(MathExpr::var("x'1").render().unwrap(), "x'1");
+        for name in ["", "x_1", "a b", r"\alpha", "50%"] {
+            assert!(matches!(
+                MathExpr::var(name).render(),
+                Err(LatexError::InvalidVariable(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn unnumbered_labels_are_rejected() {
+        let export = |latex: Latex| latex.export();
+        let equation = Equation::new(MathExpr::var("x")).unnumbered().label("eq:x");
+        assert!(matches!(
+            export(Latex::new(DocumentClass::Article).part(equation)),
+            Err(LatexError::UnnumberedLabel(label)) if label == "eq:x"
+        ));
+
+        let row = || AlignRow::new(vec![MathExpr::var("x")]).label("eq:row");
+        let notag = Align::new().row(row().unnumbered());
+        assert!(matches!(
+            export(Latex::new(DocumentClass::Article).part(notag)),
+            Err(LatexError::UnnumberedLabel(_))
+        ));
+        let starred = Align::new().unnumbered().row(row());
+        assert!(matches!(
+            export(Latex::new(DocumentClass::Article).part(starred)),
+            Err(LatexError::UnnumberedLabel(_))
+        ));
+        assert!(export(Latex::new(DocumentClass::Article).part(Align::new().row(row()))).is_ok());
+    }
+}
@@ -0,0 +1,91 @@
+use std::slice::Iter;
+
+use super::{LatexResult, ToLatex};
+
+//the options of a part in the order they were added, with at most one option of each name
+//adding an option with a name that is already set replaces the earlier one, so the last one wins
+//replacing an option with a different value is remembered as a conflict, as is writing a key
+//another option writes too, both of which `Latex::strict_options` turns into an error on export
+#[derive(Debug, Clone)]
+pub struct OptionSet<T> {
+    options: Vec<T>,
+    conflicts: Vec<&'static str>,
+}
+
+impl<T> Default for OptionSet<T> {
+    fn default() -> Self {
+        OptionSet {
+            options: Vec::new(),
+            conflicts: Vec::new(),
+        }
+    }
+}
+
+impl<T> OptionSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.options.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+
+    //the name of the first option that was set twice with different values
+    pub fn conflict(&self) -> Option<&'static str> {
+        self.conflicts.first().copied()
+    }
+}
+
+impl<T: NamedOption + PartialEq> OptionSet<T> {
+    pub fn insert(&mut self, option: T) {
+        let keys = option.keys();
+        let shared = self
+            .options
+            .iter()
+            .filter(|o| o.name() != option.name())
+            .flat_map(|o| o.keys())
+            .find(|key| keys.contains(key));
+        if let Some(key) = shared {
+            self.conflicts.push(key);
+        }
+
+        match self.options.iter_mut().find(|o| o.name() == option.name()) {
+            Some(old) => {
+                if *old != option {
+                    self.conflicts.push(option.name());
+                }
+                *old = option;
+            }
+            None => self.options.push(option),
+        }
+    }
+}
+
+impl<T: ToLatex> OptionSet<T> {
+    //the comma separated content of the `[...]` option brackets
+    pub fn export_list(&self) -> LatexResult<String> {
+        let mut options = Vec::with_capacity(self.options.len());
+        for option in self.options.iter() {
+            options.push(option.export()?.to_string());
+        }
+        Ok(options.join(", "))
+    }
+}
+
+pub trait NamedOption {
+    //the key of the option as it is written in latex, used to report conflicts
+    fn name(&self) -> &'static str;
+
+    //every key the option writes, for options that set more than one
+    fn keys(&self) -> Vec<&'static str> {
+        vec![self.name()]
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Write;
+
+use super::{LatexError, LatexLines, LatexResult, ToLatex};
+
+//packages that break each other when loaded together
+const INCOMPATIBLE: &[(&str, &str)] = &[
+    ("subcaption", "subfig"),
+    ("subcaption", "subfigure"),
+    ("subfig", "subfigure"),
+    ("enumerate", "enumitem"),
+    ("natbib", "biblatex"),
+];
+
+//packages that have to be loaded after all others, in this order
+const LOAD_LAST: &[&str] = &["hyperref", "cleveref"];
+
+//a package loaded with `\usepackage`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    name: String,
+    options: Vec<String>,
+}
+
+impl Package {
+    pub fn new(name: impl Into<String>) -> Self {
+        Package {
+            name: name.into(),
+            options: Vec::new(),
+        }
+    }
+
+    //a flag like `utf8` or a key value pair like `colorlinks=true`
+    pub fn option(mut self, option: impl Into<String>) -> Self {
+        let option = option.into();
+        if !self.options.contains(&option) {
+            self.options.push(option);
+        }
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn merge(&mut self, other: Package) {
+        for option in other.options {
+            if !self.options.contains(&option) {
+                self.options.push(option);
+            }
+        }
+    }
+
+    //the first key that got two different values
+    fn option_clash(&self) -> Option<&str> {
+        self.options.iter().enumerate().find_map(|(i, option)| {
+            self.options[..i]
+                .iter()
+                .any(|earlier| option.contains('=') && option_key(earlier) == option_key(option))
+                .then(|| option_key(option))
+        })
+    }
+
+    fn load_position(&self) -> usize {
+        LOAD_LAST
+            .iter()
+            .position(|name| *name == self.name)
+            .map_or(0, |position| position + 1)
+    }
+}
+
+fn option_key(option: &str) -> &str {
+    option.split('=').next().unwrap_or_default().trim()
+}
+
+impl ToLatex for Package {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut line = r"\usepackage".to_owned();
+        if !self.options.is_empty() {
+            write!(&mut line, "[{}]", self.options.join(", "))?;
+        }
+        write!(&mut line, "{{{}}}", self.name)?;
+        Ok(vec![line].into())
+    }
+}
+
+//the packages, tikz libraries and extra lines that go before `\begin{document}`
+//a package required twice is loaded once with the options of both
+#[derive(Debug, Clone, Default)]
+pub struct Preamble {
+    packages: Vec<Package>,
+    tikz_libraries: Vec<String>,
+    lines: Vec<String>,
+}
+
+impl Preamble {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn package(&mut self, package: Package) {
+        match self.packages.iter_mut().find(|p| p.name == package.name) {
+            Some(existing) => existing.merge(package),
+            None => self.packages.push(package),
+        }
+    }
+
+    pub fn tikz_library(&mut self, library: impl Into<String>) {
+        let library = library.into();
+        if !self.tikz_libraries.contains(&library) {
+            self.tikz_libraries.push(library);
+        }
+    }
+
+    pub fn line(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    pub fn extend(&mut self, other: Preamble) {
+        for package in other.packages {
+            self.package(package);
+        }
+        for library in other.tikz_libraries {
+            self.tikz_library(library);
+        }
+        self.lines.extend(other.lines);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.packages.iter().any(|package| package.name == name)
+    }
+
+    pub fn retain(&mut self, keep: impl Fn(&Package) -> bool) {
+        self.packages.retain(keep);
+    }
+
+    fn check(&self) -> LatexResult<()> {
+        for (first, second) in INCOMPATIBLE {
+            if self.contains(first) && self.contains(second) {
+                return Err(LatexError::IncompatiblePackages(first, second));
+            }
+        }
+        for package in self.packages.iter() {
+            if let Some(option) = package.option_clash() {
+                return Err(LatexError::PackageOptionClash {
+                    package: package.name.clone(),
+                    option: option.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ToLatex for Preamble {
+    fn export(&self) -> LatexResult<LatexLines> {
+        self.check()?;
+
+        let mut packages: Vec<&Package> = self.packages.iter().collect();
+        //stable, so all other packages keep the order they were required in
+        packages.sort_by_key(|package| package.load_position());
+
+        let mut lines = Vec::new();
+        for package in packages {
+            lines.extend(package.export()?.lines);
+        }
+        if !self.tikz_libraries.is_empty() {
+            let line = format!(r"\usetikzlibrary{{{}}}", self.tikz_libraries.join(", "));
+            lines.push(line.into());
+        }
+        for line in self.lines.iter() {
+            lines.push(line.clone().into());
+        }
+        Ok(lines.into())
+    }
+}
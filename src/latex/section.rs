@@ -0,0 +1,207 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::slice::Iter;
+
+use super::{Color, Label, LatexError, LatexLines, LatexPart, LatexResult, Text, ToLatex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SectionLevel {
+    Part,
+    Chapter,
+    Section,
+    SubSection,
+    SubSubSection,
+    Paragraph,
+}
+
+impl SectionLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SectionLevel::Part => "part",
+            SectionLevel::Chapter => "chapter",
+            SectionLevel::Section => "section",
+            SectionLevel::SubSection => "subsection",
+            SectionLevel::SubSubSection => "subsubsection",
+            SectionLevel::Paragraph => "paragraph",
+        }
+    }
+}
+
+//a heading together with everything up to the next heading of the same level
+//latex numbers the headings itself, starred ones stay unnumbered
+#[derive(Clone)]
+pub struct Section {
+    level: SectionLevel,
+    title: Text,
+    short_title: Option<Text>,
+    starred: bool,
+    label: Option<Label>,
+    parts: Vec<LatexPart>,
+}
+
+impl Section {
+    pub fn new(level: SectionLevel, title: impl Into<Text>) -> Self {
+        Section {
+            level,
+            title: title.into(),
+            short_title: None,
+            starred: false,
+            label: None,
+            parts: Vec::new(),
+        }
+    }
+
+    //the title used in the table of contents and the headers
+    pub fn short_title(mut self, short_title: impl Into<Text>) -> Self {
+        self.short_title = Some(short_title.into());
+        self
+    }
+
+    pub fn starred(mut self) -> Self {
+        self.starred = true;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<Label>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn part(mut self, part: impl Into<LatexPart>) -> Self {
+        self.parts.push(part.into());
+        self
+    }
+
+    pub fn get_label(&self) -> Option<&Label> {
+        self.label.as_ref()
+    }
+
+    //a label on a starred heading, which has no number to point to
+    pub fn unnumbered_label(&self) -> Option<&Label> {
+        self.label.as_ref().filter(|_| self.starred)
+    }
+
+    pub fn level(&self) -> SectionLevel {
+        self.level
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, LatexPart> {
+        self.parts.iter()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        let mut colors = self.title.get_colors();
+        if let Some(short_title) = &self.short_title {
+            colors.extend(short_title.get_colors());
+        }
+        colors
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        let mut texts = vec![&self.title];
+        texts.extend(self.short_title.as_ref());
+        texts
+    }
+}
+
+impl ToLatex for Section {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let name = self.level.name();
+        let title = self.title.export()?;
+
+        let mut heading = String::new();
+        match (&self.short_title, self.starred) {
+            (None, false) => write!(&mut heading, r"\{name}{{{title}}}")?,
+            (None, true) => write!(&mut heading, r"\{name}*{{{title}}}")?,
+            (Some(short_title), false) => write!(
+                &mut heading,
+                r"\{name}[{}]{{{title}}}",
+                short_title.export()?
+            )?,
+            (Some(_), true) => return Err(LatexError::ShortTitleOnStarredSection(self.level)),
+        }
+        if let Some(label) = &self.label {
+            write!(&mut heading, "{}", label.export()?)?;
+        }
+        let mut lines = vec![heading.into()];
+
+        for part in self.parts.iter() {
+            let mut part_lines = part.export()?;
+            for mut part_line in part_lines.drain(..) {
+                part_line.indentation += 1;
+                lines.push(part_line);
+            }
+        }
+
+        Ok(lines.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Section, SectionLevel};
+    use crate::latex::{DocumentClass, Latex, LatexError, ToLatex};
+
+    #[test]
+    fn sections_are_checked_against_the_class() {
+        let section = Section::new(SectionLevel::Section, "Results")
+            .label("sec:results")
+            .part(Section::new(SectionLevel::SubSection, "Runtime").starred());
+        let output = Latex::new(DocumentClass::Article)
+            .part(section)
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(
+            output.contains("\t\\section{Results}\\label{sec:results}\n\t\t\\subsection*{Runtime}")
+        );
+
+        let chapter = Latex::new(DocumentClass::Article)
+            .part(Section::new(SectionLevel::Chapter, "Intro"))
+            .export();
+        assert!(matches!(
+            chapter,
+            Err(LatexError::SectionNotAllowed(SectionLevel::Chapter))
+        ));
+
+        let inverted = Latex::new(DocumentClass::Article)
+            .part(
+                Section::new(SectionLevel::SubSection, "Outer")
+                    .part(Section::new(SectionLevel::Section, "Inner")),
+            )
+            .export();
+        assert!(matches!(
+            inverted,
+            Err(LatexError::InvalidSectionNesting { .. })
+        ));
+    }
+
+    #[test]
+    fn starred_sections_take_no_label() {
+        let section = || Section::new(SectionLevel::Section, "Intro").label("sec:intro");
+        let latex = Latex::new(DocumentClass::Article).part(section().starred());
+        assert!(matches!(
+            latex.export(),
+            Err(LatexError::UnnumberedLabel(label)) if label == "sec:intro"
+        ));
+        assert!(Latex::new(DocumentClass::Article)
+            .part(section())
+            .export()
+            .is_ok());
+    }
+
+    #[test]
+    fn starred_sections_take_no_short_title() {
+        let section = Section::new(SectionLevel::Section, "Introduction").short_title("Intro");
+        assert!(matches!(
+            section.clone().starred().export(),
+            Err(LatexError::ShortTitleOnStarredSection(
+                SectionLevel::Section
+            ))
+        ));
+        assert_eq!(
+            section.export().unwrap().to_string(),
+            r"\section[Intro]{Introduction}"
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::tikz::positive;
+use super::{
+    Color, LatexError, LatexLine, LatexLines, LatexResult, Lint, NamedColor, Package, Paint, Text,
+    ToLatex,
+};
+
+#[derive(Clone, Default)]
+pub struct Table {
+    columns: Vec<ColumnAlignment>,
+    header: Option<Vec<Cell>>,
+    rows: Vec<Vec<Cell>>,
+    //rules of the `booktabs` package instead of `\hline`
+    booktabs: bool,
+    //a `longtable` that breaks across pages and repeats its header
+    long: bool,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //a table with a column per field and a row per element of `rows`
+    pub fn from_rows<R: ToTableRow>(rows: impl IntoIterator<Item = R>) -> Self {
+        let mut table = Table::new().columns(R::columns()).header(R::header());
+        for row in rows {
+            table = table.row(row.row());
+        }
+        table
+    }
+
+    pub fn column(mut self, column: ColumnAlignment) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn columns(mut self, columns: impl IntoIterator<Item = ColumnAlignment>) -> Self {
+        self.columns.extend(columns);
+        self
+    }
+
+    pub fn header<C: Into<Cell>>(mut self, header: impl IntoIterator<Item = C>) -> Self {
+        self.header = Some(header.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    //cells covered by a cell spanning several rows have to be given as empty cells
+    pub fn row<C: Into<Cell>>(mut self, row: impl IntoIterator<Item = C>) -> Self {
+        self.rows
+            .push(row.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    pub fn booktabs(mut self) -> Self {
+        self.booktabs = true;
+        self
+    }
+
+    pub fn long(mut self) -> Self {
+        self.long = true;
+        self
+    }
+
+    fn iter_cells(&self) -> impl Iterator<Item = &Cell> {
+        self.header.iter().chain(self.rows.iter()).flatten()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        let mut colors = BTreeSet::new();
+        for cell in self.iter_cells() {
+            colors.extend(cell.content.get_colors());
+            colors.extend(cell.background.iter().flat_map(|paint| paint.get_colors()));
+        }
+        colors
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        self.iter_cells().map(|cell| &cell.content).collect()
+    }
+
+    //the packages this table needs, in the order they should be loaded
+    pub fn packages(&self) -> Vec<Package> {
+        let mut packages = Vec::new();
+        if self.booktabs {
+            packages.push("booktabs");
+        }
+        if self.long {
+            packages.push("longtable");
+        }
+        if self.columns.contains(&ColumnAlignment::Decimal) {
+            packages.push("siunitx");
+        }
+        if self.iter_cells().any(|cell| cell.rows > 1) {
+            packages.push("multirow");
+        }
+        if self.iter_cells().any(|cell| cell.background.is_some()) {
+            packages.push("xcolor");
+            packages.push("colortbl");
+        }
+        packages.into_iter().map(Package::new).collect()
+    }
+
+    //checks the text of every cell with a background, uncolored text counts as black
+    pub fn lints(&self) -> Vec<Lint> {
+        let black = Paint::from(NamedColor::Black);
+        let mut lints = Vec::new();
+        for (row, cells) in self.header.iter().chain(self.rows.iter()).enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                let Some(background) = &cell.background else {
+                    continue;
+                };
+                let mut paints = cell.content.paints();
+                if paints.is_empty() {
+                    paints.push(&black);
+                }
+                let location = format!("table cell {} in row {}", column + 1, row + 1);
+                lints.extend(
+                    paints
+                        .into_iter()
+                        .filter_map(|paint| Lint::contrast(location.clone(), paint, background)),
+                );
+            }
+        }
+        lints
+    }
+
+    fn rule(&self, booktabs_rule: &str) -> LatexLine {
+        let rule = if self.booktabs {
+            booktabs_rule
+        } else {
+            r"\hline"
+        };
+        let mut line: LatexLine = rule.to_owned().into();
+        line.indentation += 1;
+        line
+    }
+
+    fn export_row(&self, row: &[Cell]) -> LatexResult<LatexLine> {
+        let width: usize = row.iter().map(|cell| cell.columns).sum();
+        if width != self.columns.len() {
+            return Err(LatexError::TableRowWidth {
+                expected: self.columns.len(),
+                found: width,
+            });
+        }
+
+        let mut cells = Vec::with_capacity(row.len());
+        let mut column = 0;
+        for cell in row {
+            cells.push(cell.export(&self.columns[column..column + cell.columns])?);
+            column += cell.columns;
+        }
+
+        let mut line: LatexLine = format!(r"{} \\", cells.join(" & ")).into();
+        line.indentation += 1;
+        Ok(line)
+    }
+}
+
+impl ToLatex for Table {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let environment = if self.long { "longtable" } else { "tabular" };
+        let mut columns = String::new();
+        for column in self.columns.iter() {
+            write!(&mut columns, "{}", column.export()?)?;
+        }
+
+        let mut lines = Vec::new();
+        lines.push(format!(r"\begin{{{environment}}}{{{columns}}}").into());
+        lines.push(self.rule(r"\toprule"));
+        if let Some(header) = &self.header {
+            lines.push(self.export_row(header)?);
+            lines.push(self.rule(r"\midrule"));
+            if self.long {
+                let mut line: LatexLine = r"\endhead".to_owned().into();
+                line.indentation += 1;
+                lines.push(line);
+            }
+        }
+        for row in self.rows.iter() {
+            lines.push(self.export_row(row)?);
+        }
+        lines.push(self.rule(r"\bottomrule"));
+        lines.push(format!(r"\end{{{environment}}}").into());
+
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnAlignment {
+    Left,
+    Center,
+    Right,
+    //a paragraph column of the given width in cm
+    Paragraph(f32),
+    //aligned at the decimal point by `siunitx`
+    Decimal,
+}
+
+impl ToLatex for ColumnAlignment {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let alignment = match self {
+            ColumnAlignment::Left => "l".to_owned(),
+            ColumnAlignment::Center => "c".to_owned(),
+            ColumnAlignment::Right => "r".to_owned(),
+            ColumnAlignment::Paragraph(width) => format!("p{{{}cm}}", positive(*width)?),
+            ColumnAlignment::Decimal => "S".to_owned(),
+        };
+        Ok(vec![alignment].into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    content: Text,
+    columns: usize,
+    rows: usize,
+    background: Option<Paint>,
+}
+
+impl Cell {
+    pub fn new(content: impl Into<Text>) -> Self {
+        Cell {
+            content: content.into(),
+            columns: 1,
+            rows: 1,
+            background: None,
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Text::new())
+    }
+
+    //spans this and the next `columns - 1` cells of the row with `\multicolumn`
+    pub fn span_columns(mut self, columns: usize) -> Self {
+        self.columns = columns.max(1);
+        self
+    }
+
+    //spans this and the cells below with `\multirow`
+    pub fn span_rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn background(mut self, color: impl Into<Paint>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    fn export(&self, columns: &[ColumnAlignment]) -> LatexResult<String> {
+        let mut content = self.content.export()?.to_string();
+        //siunitx tries to read everything in a decimal column as a number
+        if columns == [ColumnAlignment::Decimal] && !is_plain_number(content.trim()) {
+            content = format!("{{{content}}}");
+        }
+        if self.rows > 1 {
+            content = format!(r"\multirow{{{}}}{{*}}{{{content}}}", self.rows);
+        }
+        if let Some(color) = &self.background {
+            content = format!(r"\cellcolor{{{}}}{content}", color.opaque_expression()?);
+        }
+        if self.columns > 1 {
+            let alignment = match columns[0] {
+                ColumnAlignment::Decimal => ColumnAlignment::Center,
+                alignment => alignment,
+            };
+            content = format!(
+                r"\multicolumn{{{}}}{{{}}}{{{content}}}",
+                self.columns,
+                alignment.export()?
+            );
+        }
+        Ok(content)
+    }
+}
+
+//digits with an optional sign, decimal point and exponent, `parse::<f64>` would also take `inf` or `NaN`
+fn is_plain_number(content: &str) -> bool {
+    let (mantissa, exponent) = match content.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (content, None),
+    };
+    let unsigned = |part: &str| part.strip_prefix(['+', '-']).unwrap_or(part).to_owned();
+    let mantissa = unsigned(mantissa);
+    let digits = mantissa.replacen('.', "", 1);
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let exponent = unsigned(exponent);
+        !exponent.is_empty() && exponent.chars().all(|c| c.is_ascii_digit())
+    });
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) && exponent_ok
+}
+
+impl From<Text> for Cell {
+    fn from(content: Text) -> Self {
+        Cell::new(content)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(content: &str) -> Self {
+        Cell::new(content)
+    }
+}
+
+impl From<String> for Cell {
+    fn from(content: String) -> Self {
+        Cell::new(content)
+    }
+}
+
+//turns a struct into a table row, `Table::from_rows` builds a table out of these
+pub trait ToTableRow {
+    fn columns() -> Vec<ColumnAlignment>;
+
+    fn header() -> Vec<Cell>;
+
+    fn row(&self) -> Vec<Cell>;
+}
+
+impl<T: ToTableRow> ToTableRow for &T {
+    fn columns() -> Vec<ColumnAlignment> {
+        T::columns()
+    }
+
+    fn header() -> Vec<Cell> {
+        T::header()
+    }
+
+    fn row(&self) -> Vec<Cell> {
+        T::row(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cell, ColumnAlignment, Table};
+    use crate::latex::{LatexError, TikzError, ToLatex, ToTableRow};
+
+    #[test]
+    fn tables_span_and_align() {
+        let table = Table::new()
+            .columns([ColumnAlignment::Left, ColumnAlignment::Decimal])
+            .header(["Tool", "Time_s"])
+            .row([Cell::from("a").span_rows(2), Cell::from("1.5")])
+            .row([Cell::empty(), Cell::from("n/a")])
+            .row([Cell::from("total").span_columns(2)])
+            .booktabs();
+        let expected = r"\begin{tabular}{lS}
+	\toprule
+	Tool & {Time\_s} \\
+	\midrule
+	\multirow{2}{*}{a} & 1.5 \\
+	 & {n/a} \\
+	\multicolumn{2}{l}{total} \\
+	\bottomrule
+\end{tabular}";
+        assert_eq!(table.export().unwrap().to_string(), expected);
+        let packages = table.packages();
+        let names: Vec<&str> = packages.iter().map(|package| package.name()).collect();
+        assert_eq!(names, ["booktabs", "siunitx", "multirow"]);
+
+        let short = Table::new()
+            .columns([ColumnAlignment::Left, ColumnAlignment::Right])
+            .row(["a"]);
+        assert!(matches!(
+            short.export(),
+            Err(LatexError::TableRowWidth {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[derive(ToTableRow)]
+    struct Measurement {
+        #[table(title = "Tool name")]
+        name: &'static str,
+        #[table(title = "Time", format = "{:.2}", align = "S")]
+        time: f64,
+        runs: u32,
+        #[table(skip)]
+        _note: &'static str,
+    }
+
+    #[test]
+    fn tables_from_structs() {
+        let measurements = [
+            Measurement {
+                name: "sort_a",
+                time: 1.234,
+                runs: 10,
+                _note: "",
+            },
+            Measurement {
+                name: "sort_b",
+                time: 10.0,
+                runs: 3,
+                _note: "",
+            },
+        ];
+        let expected = r"\begin{tabular}{lSr}
+	\hline
+	Tool name & {Time} & runs \\
+	\hline
+	sort\_a & 1.23 & 10 \\
+	sort\_b & 10.00 & 3 \\
+	\hline
+\end{tabular}";
+        let table = Table::from_rows(&measurements);
+        assert_eq!(table.export().unwrap().to_string(), expected);
+    }
+
+    #[test]
+    fn only_plain_numbers_stay_unbraced_in_decimal_columns() {
+        let export = |content: &str| {
+            Cell::new(content)
+                .export(&[ColumnAlignment::Decimal])
+                .unwrap()
+        };
+        for number in ["1", "-2.5", "+.5", "3.", "1e5", "1.5E-3"] {
+            assert_eq!(export(number), number);
+        }
+        for text in [
+            "NaN",
+            "inf",
+            "-infinity",
+            "1.2.3",
+            "e5",
+            "1e",
+            ".",
+            "-",
+            "n/a",
+        ] {
+            assert_eq!(export(text), format!("{{{text}}}"));
+        }
+    }
+
+    #[test]
+    fn paragraph_columns_need_a_positive_width() {
+        let table = |width: f32| {
+            Table::new()
+                .columns([ColumnAlignment::Paragraph(width)])
+                .row(["text"])
+                .export()
+        };
+        assert!(table(2.5)
+            .unwrap()
+            .to_string()
+            .contains(r"\begin{tabular}{p{2.5cm}}"));
+        for width in [0.0, -1.0] {
+            assert!(matches!(
+                table(width),
+                Err(LatexError::Tikz(TikzError::NotPositiveFloat))
+            ));
+        }
+    }
+}
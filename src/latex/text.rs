@@ -0,0 +1,445 @@
+use std::collections::BTreeSet;
+
+use derive_more::From;
+
+use super::{Color, FontSize, Label, LatexLines, LatexResult, MathExpr, Package, Paint, ToLatex};
+
+//inline text, plain strings are escaped on export so any rust string is safe to use
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Text {
+    spans: Vec<TextSpan>,
+}
+
+impl Text {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.span(TextSpan::Plain(text.into()))
+    }
+
+    //trusted latex that is written out as is
+    pub fn raw(self, latex: impl Into<String>) -> Self {
+        self.span(TextSpan::Raw(latex.into()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn span(mut self, span: impl Into<TextSpan>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    //styles nest, so `text` may contain styled spans itself
+    pub fn styled(self, style: Style, text: impl Into<Text>) -> Self {
+        self.span(TextSpan::Styled(style, text.into()))
+    }
+
+    pub fn emph(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::Emphasis, text)
+    }
+
+    pub fn bold(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::Bold, text)
+    }
+
+    pub fn italic(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::Italic, text)
+    }
+
+    pub fn typewriter(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::Typewriter, text)
+    }
+
+    pub fn small_caps(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::SmallCaps, text)
+    }
+
+    pub fn sized(self, size: FontSize, text: impl Into<Text>) -> Self {
+        self.styled(Style::Size(size), text)
+    }
+
+    pub fn colored(self, color: impl Into<Paint>, text: impl Into<Text>) -> Self {
+        self.styled(Style::Color(color.into()), text)
+    }
+
+    pub fn link(self, url: impl Into<String>, text: impl Into<Text>) -> Self {
+        self.styled(Style::Link(url.into()), text)
+    }
+
+    //only in beamer
+    pub fn alert(self, text: impl Into<Text>) -> Self {
+        self.styled(Style::Alert, text)
+    }
+
+    //inline math between `$`
+    pub fn math(self, expr: impl Into<MathExpr>) -> Self {
+        self.span(TextSpan::Math(expr.into()))
+    }
+
+    //a reference to the label of a section, equation or figure,
+    //exporting the document fails if nothing has that label
+    pub fn reference(self, kind: RefKind, label: impl Into<Label>) -> Self {
+        self.span(TextSpan::Reference(kind, label.into()))
+    }
+
+    pub fn references(&self) -> Vec<(RefKind, &Label)> {
+        self.spans
+            .iter()
+            .flat_map(|span| match span {
+                TextSpan::Reference(kind, label) => vec![(*kind, label)],
+                TextSpan::Styled(_, inner) => inner.references(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        self.spans
+            .iter()
+            .flat_map(|span| span.get_colors())
+            .collect()
+    }
+
+    //the colors the text is written in, the default color is not included
+    pub fn paints(&self) -> Vec<&Paint> {
+        self.spans
+            .iter()
+            .flat_map(|span| match span {
+                TextSpan::Styled(style, inner) => {
+                    let mut paints = match style {
+                        Style::Color(paint) => vec![paint],
+                        _ => Vec::new(),
+                    };
+                    paints.extend(inner.paints());
+                    paints
+                }
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    //the text as it reads, without escaping or styles, for messages
+    pub fn plain(&self) -> String {
+        self.spans.iter().map(|span| span.plain()).collect()
+    }
+
+    pub fn has_links(&self) -> bool {
+        self.spans.iter().any(|span| match span {
+            TextSpan::Styled(Style::Link(_), _) => true,
+            TextSpan::Styled(_, inner) => inner.has_links(),
+            _ => false,
+        })
+    }
+
+    //true for named colors too, which have nothing to define
+    pub fn has_colors(&self) -> bool {
+        self.spans.iter().any(|span| match span {
+            TextSpan::Styled(Style::Color(_), _) => true,
+            TextSpan::Styled(_, inner) => inner.has_colors(),
+            _ => false,
+        })
+    }
+
+    pub fn has_alerts(&self) -> bool {
+        self.spans.iter().any(|span| match span {
+            TextSpan::Styled(Style::Alert, _) => true,
+            TextSpan::Styled(_, inner) => inner.has_alerts(),
+            _ => false,
+        })
+    }
+
+    pub fn has_math(&self) -> bool {
+        self.spans.iter().any(|span| match span {
+            TextSpan::Math(_) => true,
+            TextSpan::Styled(_, inner) => inner.has_math(),
+            _ => false,
+        })
+    }
+
+    pub fn packages(&self) -> Vec<Package> {
+        let references = self.references();
+        let uses = |kind: RefKind| references.iter().any(|(k, _)| *k == kind);
+
+        let mut packages = Vec::new();
+        if self.has_math() || uses(RefKind::EqRef) {
+            packages.push(Package::new("amsmath"));
+            packages.push(Package::new("amssymb"));
+        }
+        if self.has_links() || uses(RefKind::AutoRef) || uses(RefKind::CRef) {
+            packages.push(Package::new("hyperref"));
+        }
+        if uses(RefKind::CRef) {
+            packages.push(Package::new("cleveref"));
+        }
+        if self.has_colors() {
+            packages.push(Package::new("xcolor"));
+        }
+        packages
+    }
+
+    fn export_inline(&self) -> LatexResult<String> {
+        let mut latex = String::new();
+        for span in self.spans.iter() {
+            latex.push_str(&span.export_inline()?);
+        }
+        Ok(latex)
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::new().text(text)
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Text::new().text(text)
+    }
+}
+
+impl ToLatex for Text {
+    fn export(&self) -> LatexResult<LatexLines> {
+        Ok(vec![self.export_inline()?].into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, From)]
+pub enum TextSpan {
+    #[from]
+    Plain(String),
+    Raw(String),
+    Styled(Style, Text),
+    Math(MathExpr),
+    Reference(RefKind, Label),
+}
+
+impl TextSpan {
+    fn export_inline(&self) -> LatexResult<String> {
+        match self {
+            TextSpan::Plain(text) => Ok(escape(text)),
+            TextSpan::Raw(latex) => Ok(latex.clone()),
+            TextSpan::Math(expr) => Ok(format!("${}$", expr.render()?)),
+            TextSpan::Reference(kind, label) => {
+                Ok(format!(r"\{}{{{}}}", kind.command(), label.checked_name()?))
+            }
+            TextSpan::Styled(style, inner) => {
+                let inner = inner.export_inline()?;
+                let styled = match style {
+                    Style::Emphasis => format!(r"\emph{{{inner}}}"),
+                    Style::Bold => format!(r"\textbf{{{inner}}}"),
+                    Style::Italic => format!(r"\textit{{{inner}}}"),
+                    Style::Typewriter => format!(r"\texttt{{{inner}}}"),
+                    Style::SmallCaps => format!(r"\textsc{{{inner}}}"),
+                    Style::Size(size) => format!("{{{} {inner}}}", size.command()),
+                    Style::Color(color) => {
+                        format!(r"\textcolor{{{}}}{{{inner}}}", color.opaque_expression()?)
+                    }
+                    Style::Link(url) => format!(r"\href{{{}}}{{{inner}}}", escape_url(url)),
+                    Style::Alert => format!(r"\alert{{{inner}}}"),
+                };
+                Ok(styled)
+            }
+        }
+    }
+
+    fn plain(&self) -> String {
+        match self {
+            TextSpan::Plain(text) | TextSpan::Raw(text) => text.clone(),
+            TextSpan::Math(expr) => expr
+                .render()
+                .map(|math| format!("${math}$"))
+                .unwrap_or_default(),
+            TextSpan::Reference(_, label) => label.name().to_owned(),
+            TextSpan::Styled(_, inner) => inner.plain(),
+        }
+    }
+
+    fn get_colors(&self) -> BTreeSet<Color> {
+        match self {
+            TextSpan::Plain(_)
+            | TextSpan::Raw(_)
+            | TextSpan::Math(_)
+            | TextSpan::Reference(_, _) => BTreeSet::new(),
+            TextSpan::Styled(style, inner) => {
+                let mut colors = inner.get_colors();
+                if let Style::Color(color) = style {
+                    colors.extend(color.get_colors());
+                }
+                colors
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Style {
+    Emphasis,
+    Bold,
+    Italic,
+    Typewriter,
+    SmallCaps,
+    Size(FontSize),
+    Color(Paint),
+    //needs the `hyperref` package
+    Link(String),
+    //the highlight color of the beamer theme
+    Alert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RefKind {
+    //just the number
+    Ref,
+    //the number with the name of the thing in front, needs `hyperref`
+    AutoRef,
+    //like `AutoRef` but from the `cleveref` package
+    CRef,
+    PageRef,
+    //the number in parentheses, needs `amsmath`
+    EqRef,
+}
+
+impl RefKind {
+    fn command(&self) -> &'static str {
+        match self {
+            RefKind::Ref => "ref",
+            RefKind::AutoRef => "autoref",
+            RefKind::CRef => "cref",
+            RefKind::PageRef => "pageref",
+            RefKind::EqRef => "eqref",
+        }
+    }
+}
+
+//urls keep their characters, only those hyperref can not take in an argument are escaped
+fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '#' | '%' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '{' => escaped.push_str(r"\%7B"),
+            '}' => escaped.push_str(r"\%7D"),
+            '\\' => escaped.push_str(r"\%5C"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+//escapes everything latex would interpret in `text`, so it is printed as written
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            //these are replaced by other glyphs in the default OT1 font encoding
+            '<' => escaped.push_str(r"\textless{}"),
+            '>' => escaped.push_str(r"\textgreater{}"),
+            '|' => escaped.push_str(r"\textbar{}"),
+            //unicode characters the utf8 inputenc has no definition for
+            c => match escape_unicode(c) {
+                Some(command) => escaped.push_str(command),
+                None => escaped.push(c),
+            },
+        }
+    }
+    escaped
+}
+
+fn escape_unicode(c: char) -> Option<&'static str> {
+    let command = match c {
+        '\u{a0}' => "~",
+        '\u{2009}' => r"\,",
+        '…' => r"\ldots{}",
+        '°' => r"\textdegree{}",
+        '€' => r"\texteuro{}",
+        '×' => r"\texttimes{}",
+        '±' => r"\textpm{}",
+        'µ' => r"\textmu{}",
+        '−' => r"\textminus{}",
+        '→' => r"\ensuremath{\rightarrow}",
+        '←' => r"\ensuremath{\leftarrow}",
+        '↔' => r"\ensuremath{\leftrightarrow}",
+        '⇒' => r"\ensuremath{\Rightarrow}",
+        '≤' => r"\ensuremath{\leq}",
+        '≥' => r"\ensuremath{\geq}",
+        '≠' => r"\ensuremath{\neq}",
+        '≈' => r"\ensuremath{\approx}",
+        '∞' => r"\ensuremath{\infty}",
+        'α' => r"\ensuremath{\alpha}",
+        'β' => r"\ensuremath{\beta}",
+        'γ' => r"\ensuremath{\gamma}",
+        'δ' => r"\ensuremath{\delta}",
+        'ε' => r"\ensuremath{\varepsilon}",
+        'ζ' => r"\ensuremath{\zeta}",
+        'η' => r"\ensuremath{\eta}",
+        'θ' => r"\ensuremath{\theta}",
+        'ι' => r"\ensuremath{\iota}",
+        'κ' => r"\ensuremath{\kappa}",
+        'λ' => r"\ensuremath{\lambda}",
+        'μ' => r"\ensuremath{\mu}",
+        'ν' => r"\ensuremath{\nu}",
+        'ξ' => r"\ensuremath{\xi}",
+        'π' => r"\ensuremath{\pi}",
+        'ρ' => r"\ensuremath{\rho}",
+        'σ' => r"\ensuremath{\sigma}",
+        'τ' => r"\ensuremath{\tau}",
+        'υ' => r"\ensuremath{\upsilon}",
+        'φ' => r"\ensuremath{\varphi}",
+        'χ' => r"\ensuremath{\chi}",
+        'ψ' => r"\ensuremath{\psi}",
+        'ω' => r"\ensuremath{\omega}",
+        'Γ' => r"\ensuremath{\Gamma}",
+        'Δ' => r"\ensuremath{\Delta}",
+        'Θ' => r"\ensuremath{\Theta}",
+        'Λ' => r"\ensuremath{\Lambda}",
+        'Ξ' => r"\ensuremath{\Xi}",
+        'Π' => r"\ensuremath{\Pi}",
+        'Σ' => r"\ensuremath{\Sigma}",
+        'Φ' => r"\ensuremath{\Phi}",
+        'Ψ' => r"\ensuremath{\Psi}",
+        'Ω' => r"\ensuremath{\Omega}",
+        _ => return None,
+    };
+    Some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Text;
+    use crate::latex::{Color, ToLatex};
+
+    #[test]
+    fn text_is_escaped() {
+        let text = Text::from(r"50% of a_b & {c} #1 $x^2~\ <α>").raw(r" \LaTeX");
+        assert_eq!(
+            text.export().unwrap().to_string(),
+            r"50\% of a\_b \& \{c\} \#1 \$x\textasciicircum{}2\textasciitilde{}\textbackslash{} \textless{}\ensuremath{\alpha}\textgreater{} \LaTeX"
+        );
+    }
+
+    #[test]
+    fn styles_nest() {
+        let red = Color { r: 200, g: 0, b: 0 };
+        let text = Text::new().bold(Text::from("a_").colored(red, "b%"));
+        assert_eq!(
+            text.export().unwrap().to_string(),
+            r"\textbf{a\_\textcolor{0xc80000}{b\%}}"
+        );
+        assert!(text.get_colors().contains(&red));
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::BTreeSet;
+
+use super::tikz::{KIT_BLACK, KIT_BLUE, KIT_GREEN, KIT_RED};
+use super::{Color, LatexLines, LatexResult, Lint, NamedColor, Paint, Text, ToLatex};
+
+//how a beamer presentation looks, every theme left out keeps the beamer default
+#[derive(Clone)]
+pub struct BeamerTheme {
+    theme: Option<String>,
+    color_theme: Option<String>,
+    inner_theme: Option<String>,
+    outer_theme: Option<String>,
+    font_theme: Option<String>,
+    navigation_symbols: bool,
+    footline: Option<Footline>,
+    //in the order they were set, one entry per element
+    colors: Vec<(String, BeamerColor)>,
+}
+
+impl Default for BeamerTheme {
+    fn default() -> Self {
+        BeamerTheme {
+            theme: None,
+            color_theme: None,
+            inner_theme: None,
+            outer_theme: None,
+            font_theme: None,
+            navigation_symbols: true,
+            footline: None,
+            colors: Vec::new(),
+        }
+    }
+}
+
+impl BeamerTheme {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //green structure, red alerts and the frame number in the corner, without navigation symbols
+    pub fn kit() -> Self {
+        Self::new()
+            .inner_theme("rectangles")
+            .navigation_symbols(false)
+            .footline(Footline::FrameNumber)
+            .color("normal text", BeamerColor::new().fg(KIT_BLACK))
+            .color("structure", BeamerColor::new().fg(KIT_GREEN))
+            .color("alerted text", BeamerColor::new().fg(KIT_RED))
+            //white on the plain KIT green is too faint for small text
+            .color(
+                "block title",
+                BeamerColor::new()
+                    .fg(NamedColor::White)
+                    .bg(Paint::new(KIT_GREEN).mix(80, KIT_BLACK)),
+            )
+            .color(
+                "block title alerted",
+                BeamerColor::new().fg(NamedColor::White).bg(KIT_RED),
+            )
+            .color(
+                "block title example",
+                BeamerColor::new().fg(NamedColor::White).bg(KIT_BLUE),
+            )
+    }
+
+    pub fn theme(mut self, theme: impl Into<String>) -> Self {
+        self.theme = Some(theme.into());
+        self
+    }
+
+    pub fn color_theme(mut self, theme: impl Into<String>) -> Self {
+        self.color_theme = Some(theme.into());
+        self
+    }
+
+    pub fn inner_theme(mut self, theme: impl Into<String>) -> Self {
+        self.inner_theme = Some(theme.into());
+        self
+    }
+
+    pub fn outer_theme(mut self, theme: impl Into<String>) -> Self {
+        self.outer_theme = Some(theme.into());
+        self
+    }
+
+    pub fn font_theme(mut self, theme: impl Into<String>) -> Self {
+        self.font_theme = Some(theme.into());
+        self
+    }
+
+    pub fn navigation_symbols(mut self, shown: bool) -> Self {
+        self.navigation_symbols = shown;
+        self
+    }
+
+    pub fn footline(mut self, footline: Footline) -> Self {
+        self.footline = Some(footline);
+        self
+    }
+
+    //sets the colors of a beamer element like `structure` or `frametitle`,
+    //setting the same element again replaces the earlier colors
+    pub fn color(mut self, element: impl Into<String>, color: BeamerColor) -> Self {
+        let element = element.into();
+        match self.colors.iter_mut().find(|(e, _)| *e == element) {
+            Some((_, old)) => *old = color,
+            None => self.colors.push((element, color)),
+        }
+        self
+    }
+
+    pub fn texts(&self) -> Vec<&Text> {
+        match &self.footline {
+            Some(Footline::Custom(text)) => vec![text],
+            _ => Vec::new(),
+        }
+    }
+
+    //only elements with both colors set, the other color comes from the theme
+    pub fn lints(&self) -> Vec<Lint> {
+        self.colors
+            .iter()
+            .filter_map(|(element, color)| match (&color.fg, &color.bg) {
+                (Some(fg), Some(bg)) => {
+                    Lint::contrast(format!("beamer color \"{element}\""), fg, bg)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        let mut colors: BTreeSet<Color> = self
+            .colors
+            .iter()
+            .flat_map(|(_, color)| color.fg.iter().chain(color.bg.iter()))
+            .flat_map(|paint| paint.get_colors())
+            .collect();
+        for text in self.texts() {
+            colors.extend(text.get_colors());
+        }
+        colors
+    }
+}
+
+impl ToLatex for BeamerTheme {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut lines = Vec::new();
+        let themes = [
+            ("usetheme", &self.theme),
+            ("usecolortheme", &self.color_theme),
+            ("useinnertheme", &self.inner_theme),
+            ("useoutertheme", &self.outer_theme),
+            ("usefonttheme", &self.font_theme),
+        ];
+        for (command, theme) in themes {
+            if let Some(theme) = theme {
+                lines.push(format!(r"\{command}{{{theme}}}"));
+            }
+        }
+        if !self.navigation_symbols {
+            lines.push(r"\setbeamertemplate{navigation symbols}{}".to_owned());
+        }
+        if let Some(footline) = &self.footline {
+            lines.push(footline.export()?.to_string());
+        }
+        for (element, color) in self.colors.iter() {
+            let mut values = Vec::new();
+            if let Some(fg) = &color.fg {
+                values.push(format!("fg={}", fg.opaque_expression()?));
+            }
+            if let Some(bg) = &color.bg {
+                values.push(format!("bg={}", bg.opaque_expression()?));
+            }
+            lines.push(format!(
+                r"\setbeamercolor{{{element}}}{{{}}}",
+                values.join(", ")
+            ));
+        }
+        Ok(lines.into())
+    }
+}
+
+//the line at the bottom of every slide
+#[derive(Clone)]
+pub enum Footline {
+    Empty,
+    FrameNumber,
+    PageNumber,
+    //author, title, date and frame number, loads the `infolines` outer theme
+    InfoLines,
+    Custom(Text),
+}
+
+impl ToLatex for Footline {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let line = match self {
+            Footline::Empty => r"\setbeamertemplate{footline}{}".to_owned(),
+            Footline::FrameNumber => r"\setbeamertemplate{footline}[frame number]".to_owned(),
+            Footline::PageNumber => r"\setbeamertemplate{footline}[page number]".to_owned(),
+            //beamer has no such footline template, only the outer theme draws it
+            Footline::InfoLines => r"\useoutertheme{infolines}".to_owned(),
+            Footline::Custom(text) => {
+                format!(r"\setbeamertemplate{{footline}}{{{}}}", text.export()?)
+            }
+        };
+        Ok(vec![line].into())
+    }
+}
+
+//the foreground and background of a beamer element, unset ones are inherited
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BeamerColor {
+    fg: Option<Paint>,
+    bg: Option<Paint>,
+}
+
+impl BeamerColor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fg(mut self, color: impl Into<Paint>) -> Self {
+        self.fg = Some(color.into());
+        self
+    }
+
+    pub fn bg(mut self, color: impl Into<Paint>) -> Self {
+        self.bg = Some(color.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BeamerColor, BeamerTheme, Footline};
+    use crate::latex::{Color, DocumentClass, Latex, LatexError, Text, ToLatex};
+
+    #[test]
+    fn beamer_themes_are_configured() {
+        let green = Color { r: 0, g: 200, b: 0 };
+        let theme = BeamerTheme::new()
+            .theme("Madrid")
+            .font_theme("serif")
+            .navigation_symbols(false)
+            .footline(Footline::Custom(Text::new().raw(r"\insertframenumber")))
+            .color("structure", BeamerColor::new().fg(green));
+        let output = Latex::new(DocumentClass::Beamer)
+            .beamer_theme(theme)
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(output.contains(
+            r"\usepackage[utf8]{inputenc}
+\definecolor{0x00c800}{rgb}{0, 0.78431374, 0}
+\usetheme{Madrid}
+\usefonttheme{serif}
+\setbeamertemplate{navigation symbols}{}
+\setbeamertemplate{footline}{\insertframenumber}
+\setbeamercolor{structure}{fg=0x00c800}
+\begin{document}"
+        ));
+
+        let kit = Latex::new(DocumentClass::Beamer)
+            .beamer_theme(BeamerTheme::kit())
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(kit.contains(r"\setbeamercolor{structure}{fg=0x009682}"));
+        assert!(kit.contains(r"\definecolor{0x009682}"));
+
+        let article = Latex::new(DocumentClass::Article)
+            .beamer_theme(BeamerTheme::kit())
+            .export();
+        assert!(matches!(
+            article,
+            Err(LatexError::PartNotAllowed("beamer theme"))
+        ));
+    }
+
+    #[test]
+    fn info_lines_load_the_outer_theme() {
+        let theme = BeamerTheme::new().footline(Footline::InfoLines);
+        assert_eq!(
+            theme.export().unwrap().to_string(),
+            r"\useoutertheme{infolines}"
+        );
+    }
+
+    #[test]
+    fn kit_preset_is_readable() {
+        assert_eq!(BeamerTheme::kit().lints(), []);
+    }
+}
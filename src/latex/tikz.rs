@@ -1,26 +1,44 @@
-pub use super::{LatexLine, LatexLines, LatexResult, ToLatex};
+pub use super::{LatexLines, LatexResult, Lint, NamedOption, OptionSet, Package, ToLatex};
 
+use super::{ClassOption, DocumentClass, Latex, LatexError, Text};
 use simple_math::Vec2;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fmt::Write;
-use std::hash::{Hash, Hasher};
-use std::mem::discriminant;
+use std::path::Path;
+use std::process::Output;
 
 mod error;
 pub use error::TikzError;
 
 mod color;
 pub use color::Color;
+pub use color::{
+    KIT_BLACK, KIT_BLACK_70, KIT_BLUE, KIT_BROWN, KIT_CYAN, KIT_GREEN, KIT_MAY_GREEN, KIT_ORANGE,
+    KIT_PURPLE, KIT_RED, KIT_YELLOW,
+};
+
+mod paint;
+pub use paint::{BaseColor, NamedColor, Paint};
+
+mod brewer;
+pub use brewer::{Brewer, BrewerKind};
+
+mod palette;
+pub use palette::Palette;
 
 mod tikz_part;
 pub use tikz_part::{
-    Line, LineOption, Node, NodeOptions, Polygon, PolygonOption, TikzPart, Visible,
+    Anchor, Arc, Arrow, ArrowDirection, ArrowTip, Circle, DashPattern, Direction, Ellipse, Line,
+    LineCap, LineJoin, LineOption, Node, NodeOptions, NodeShape, OverlayMode, OverlaySpec, Polygon,
+    PolygonOption, Rectangle, RelativePosition, ShapeOption, TikzPart, Visible,
 };
 
 #[derive(Default, Clone)]
 pub struct Tikz {
+    //used as the file name of the standalone export
+    name: Option<String>,
     parts: Vec<TikzPart>,
-    options: HashSet<TikzOption>,
+    options: OptionSet<TikzOption>,
 }
 
 impl Tikz {
@@ -28,32 +46,99 @@ impl Tikz {
         Self::default()
     }
 
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     pub fn part(mut self, part: impl Into<TikzPart>) -> Self {
         self.parts.push(part.into());
         self
     }
 
+    //a document with just this picture, cropped to it with `border` pt of space around it
+    pub fn standalone(&self, border: Option<f32>) -> Latex {
+        let mut latex = Latex::new(DocumentClass::Standalone).part(self.clone());
+        if let Some(border) = border {
+            latex = latex.class_option(ClassOption::Border(border));
+        }
+        latex
+    }
+
+    //writes `<name>.tex` into the directory and compiles it to `<name>.pdf`
+    pub fn compile_standalone(&self, dir: &Path, border: Option<f32>) -> LatexResult<Output> {
+        let name = self.name.as_ref().ok_or(LatexError::UnnamedTikz)?;
+        let path = dir.join(format!("{name}.tex"));
+        self.standalone(border).write_and_compile(&path)
+    }
+
     pub fn option(mut self, option: impl Into<TikzOption>) -> Self {
         self.options.insert(option.into());
         self
     }
 
-    pub fn get_colors(&self) -> HashSet<Color> {
+    pub fn get_colors(&self) -> BTreeSet<Color> {
         self.parts
             .iter()
             .flat_map(|part| part.get_colors())
             .collect()
     }
+
+    pub fn packages(&self) -> Vec<Package> {
+        vec![Package::new("tikz")]
+    }
+
+    pub fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.iter_full())
+            .flat_map(|part| part.tikz_libraries())
+            .collect()
+    }
+
+    //the texts of the nodes
+    pub fn texts(&self) -> Vec<&Text> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.iter_full())
+            .flat_map(|part| part.texts())
+            .collect()
+    }
+
+    pub fn lints(&self) -> Vec<Lint> {
+        self.parts
+            .iter()
+            .flat_map(|part| part.iter_full())
+            .flat_map(|part| part.lints())
+            .collect()
+    }
+
+    pub fn has_overlays(&self) -> bool {
+        self.parts
+            .iter()
+            .flat_map(|part| part.iter_full())
+            .any(|part| matches!(part, TikzPart::Visible(_)))
+    }
+
+    //fails on the first option that was set twice with different values on the same part
+    pub fn check_conflicts(&self) -> LatexResult<()> {
+        if let Some(name) = self.options.conflict() {
+            return Err(TikzError::ConflictingOption(name).into());
+        }
+        for part in self.parts.iter().flat_map(|part| part.iter_full()) {
+            if let Some(name) = part.option_conflict() {
+                return Err(TikzError::ConflictingOption(name).into());
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ToLatex for Tikz {
     fn export(&self) -> LatexResult<LatexLines> {
         let mut first_line = String::new();
         write!(&mut first_line, r"\begin{{tikzpicture}}[")?;
-        for option in self.options.iter() {
-            write!(&mut first_line, "{}", option.export()?)?;
-        }
-        first_line.push(']');
+        write!(&mut first_line, "{}]", self.options.export_list()?)?;
         let last_line = r"\end{tikzpicture}".to_owned();
 
         let mut lines = Vec::new();
@@ -72,7 +157,7 @@ impl ToLatex for Tikz {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum TikzOption {
     Scale(f32),
 }
@@ -80,28 +165,40 @@ pub enum TikzOption {
 impl ToLatex for TikzOption {
     fn export(&self) -> LatexResult<LatexLines> {
         match self {
-            TikzOption::Scale(scale) => {
-                if scale.is_finite() {
-                    Ok(vec![format!("scale={scale}")].into())
-                } else {
-                    Err(TikzError::NotFiniteFloat.into())
-                }
-            }
+            TikzOption::Scale(scale) => Ok(vec![format!("scale={}", finite(*scale)?)].into()),
         }
     }
 }
 
-impl PartialEq for TikzOption {
-    fn eq(&self, other: &Self) -> bool {
-        discriminant(self) == discriminant(other)
+impl NamedOption for TikzOption {
+    fn name(&self) -> &'static str {
+        match self {
+            TikzOption::Scale(_) => "scale",
+        }
     }
 }
 
-impl Eq for TikzOption {}
+pub(crate) fn finite<F: Into<f64> + Copy>(value: F) -> LatexResult<F> {
+    if value.into().is_finite() {
+        Ok(value)
+    } else {
+        Err(TikzError::NotFiniteFloat.into())
+    }
+}
+
+pub(crate) fn positive<F: Into<f64> + Copy>(value: F) -> LatexResult<F> {
+    if finite(value)?.into() > 0.0 {
+        Ok(value)
+    } else {
+        Err(TikzError::NotPositiveFloat.into())
+    }
+}
 
-impl Hash for TikzOption {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        discriminant(self).hash(state);
+pub(crate) fn opacity(value: f32) -> LatexResult<f32> {
+    if (0.0..=1.0).contains(&finite(value)?) {
+        Ok(value)
+    } else {
+        Err(TikzError::OpacityOutOfRange.into())
     }
 }
 
@@ -124,3 +221,36 @@ where
 pub trait AddOption<T> {
     fn option(self, option: T) -> Self;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{AddOption, AddPointByParts, Color, Polygon, Tikz, ToLatex};
+    use crate::latex::LatexError;
+
+    #[test]
+    fn tikz_exports_standalone() {
+        let red = Color { r: 200, g: 0, b: 0 };
+        let tikz = Tikz::new()
+            .name("triangle")
+            .part(Polygon::new().option(red).point(0, 0).point(1, 0));
+        let expected = r"\documentclass[border=2pt]{standalone}
+\usepackage[utf8]{inputenc}
+\usepackage{tikz}
+\usepackage{xcolor}
+\definecolor{0xc80000}{rgb}{0.78431374, 0, 0}
+\begin{document}
+	\begin{tikzpicture}[]
+		\fill[color=0xc80000] (0, 0) -- (1, 0) -- cycle;
+	\end{tikzpicture}
+\end{document}";
+        assert_eq!(
+            tikz.standalone(Some(2.0)).export().unwrap().to_string(),
+            expected
+        );
+
+        let unnamed = Tikz::new().compile_standalone(Path::new("."), None);
+        assert!(matches!(unnamed, Err(LatexError::UnnamedTikz)));
+    }
+}
@@ -0,0 +1,141 @@
+//the color schemes of colorbrewer2.org by Cynthia Brewer, in their largest size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Brewer {
+    Set1,
+    Set2,
+    Dark2,
+    Paired,
+    Blues,
+    Greens,
+    Oranges,
+    Reds,
+    Purples,
+    RdBu,
+    PuOr,
+    BrBG,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrewerKind {
+    //unrelated categories
+    Qualitative,
+    //ordered data from low to high
+    Sequential,
+    //data around a meaningful center
+    Diverging,
+}
+
+impl Brewer {
+    pub const ALL: [Brewer; 12] = [
+        Brewer::Set1,
+        Brewer::Set2,
+        Brewer::Dark2,
+        Brewer::Paired,
+        Brewer::Blues,
+        Brewer::Greens,
+        Brewer::Oranges,
+        Brewer::Reds,
+        Brewer::Purples,
+        Brewer::RdBu,
+        Brewer::PuOr,
+        Brewer::BrBG,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Brewer::Set1 => "set1",
+            Brewer::Set2 => "set2",
+            Brewer::Dark2 => "dark2",
+            Brewer::Paired => "paired",
+            Brewer::Blues => "blues",
+            Brewer::Greens => "greens",
+            Brewer::Oranges => "oranges",
+            Brewer::Reds => "reds",
+            Brewer::Purples => "purples",
+            Brewer::RdBu => "rdbu",
+            Brewer::PuOr => "puor",
+            Brewer::BrBG => "brbg",
+        }
+    }
+
+    pub fn kind(&self) -> BrewerKind {
+        match self {
+            Brewer::Set1 | Brewer::Set2 | Brewer::Dark2 | Brewer::Paired => BrewerKind::Qualitative,
+            Brewer::Blues | Brewer::Greens | Brewer::Oranges | Brewer::Reds | Brewer::Purples => {
+                BrewerKind::Sequential
+            }
+            Brewer::RdBu | Brewer::PuOr | Brewer::BrBG => BrewerKind::Diverging,
+        }
+    }
+
+    pub(super) fn hex_colors(&self) -> &'static [u32] {
+        match self {
+            Brewer::Set1 => &[
+                0xe41a1c, 0x377eb8, 0x4daf4a, 0x984ea3, 0xff7f00, 0xffff33, 0xa65628, 0xf781bf,
+                0x999999,
+            ],
+            Brewer::Set2 => &[
+                0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3,
+            ],
+            Brewer::Dark2 => &[
+                0x1b9e77, 0xd95f02, 0x7570b3, 0xe7298a, 0x66a61e, 0xe6ab02, 0xa6761d, 0x666666,
+            ],
+            Brewer::Paired => &[
+                0xa6cee3, 0x1f78b4, 0xb2df8a, 0x33a02c, 0xfb9a99, 0xe31a1c, 0xfdbf6f, 0xff7f00,
+                0xcab2d6, 0x6a3d9a, 0xffff99, 0xb15928,
+            ],
+            Brewer::Blues => &[
+                0xf7fbff, 0xdeebf7, 0xc6dbef, 0x9ecae1, 0x6baed6, 0x4292c6, 0x2171b5, 0x08519c,
+                0x08306b,
+            ],
+            Brewer::Greens => &[
+                0xf7fcf5, 0xe5f5e0, 0xc7e9c0, 0xa1d99b, 0x74c476, 0x41ab5d, 0x238b45, 0x006d2c,
+                0x00441b,
+            ],
+            Brewer::Oranges => &[
+                0xfff5eb, 0xfee6ce, 0xfdd0a2, 0xfdae6b, 0xfd8d3c, 0xf16913, 0xd94801, 0xa63603,
+                0x7f2704,
+            ],
+            Brewer::Reds => &[
+                0xfff5f0, 0xfee0d2, 0xfcbba1, 0xfc9272, 0xfb6a4a, 0xef3b2c, 0xcb181d, 0xa50f15,
+                0x67000d,
+            ],
+            Brewer::Purples => &[
+                0xfcfbfd, 0xefedf5, 0xdadaeb, 0xbcbddc, 0x9e9ac8, 0x807dba, 0x6a51a3, 0x54278f,
+                0x3f007d,
+            ],
+            Brewer::RdBu => &[
+                0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de,
+                0x4393c3, 0x2166ac, 0x053061,
+            ],
+            Brewer::PuOr => &[
+                0x7f3b08, 0xb35806, 0xe08214, 0xfdb863, 0xfee0b6, 0xf7f7f7, 0xd8daeb, 0xb2abd2,
+                0x8073ac, 0x542788, 0x2d004b,
+            ],
+            Brewer::BrBG => &[
+                0x543005, 0x8c510a, 0xbf812d, 0xdfc27d, 0xf6e8c3, 0xf5f5f5, 0xc7eae5, 0x80cdc1,
+                0x35978f, 0x01665e, 0x003c30,
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Brewer, BrewerKind};
+    use crate::latex::{Color, Palette};
+
+    #[test]
+    fn palettes_are_built_in() {
+        assert_eq!(Palette::named("okabe-ito").unwrap().len(), 8);
+        let rdbu = Palette::named("rdbu").unwrap();
+        assert_eq!(rdbu, Palette::brewer(Brewer::RdBu));
+        assert_eq!(Brewer::RdBu.kind(), BrewerKind::Diverging);
+        let viridis = Palette::viridis(3).unwrap();
+        assert_eq!(viridis.color(0), Color::from_hex(0x440154));
+        assert_eq!(viridis.color(1), Color::from_hex(0x21918c));
+        assert_eq!(viridis.color(2), Color::from_hex(0xfde725));
+        assert_eq!(Palette::cividis(4).unwrap().len(), 4);
+        assert!(Palette::viridis(0).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::latex::LatexLine;
+use std::str::FromStr;
+
+use crate::latex::{LatexError, LatexLine};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Color {
@@ -20,25 +22,146 @@ impl Color {
 
         format!("\\definecolor{{{name}}}{{rgb}}{{{r}, {g}, {b}}}").into()
     }
+
+    //`0xRRGGBB`
+    pub const fn from_hex(hex: u32) -> Color {
+        Color {
+            r: (hex >> 16) as u8,
+            g: (hex >> 8) as u8,
+            b: hex as u8,
+        }
+    }
+
+    //each channel from 0 to 1
+    pub fn from_rgb_fractions(r: f32, g: f32, b: f32) -> Color {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+        }
+    }
+
+    //hue in degrees, saturation and lightness from 0 to 1
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        Self::from_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    //hue in degrees, saturation and value from 0 to 1
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let chroma = value.clamp(0.0, 1.0) * saturation.clamp(0.0, 1.0);
+        Self::from_chroma(hue, chroma, value.clamp(0.0, 1.0) - chroma)
+    }
+
+    fn from_chroma(hue: f32, chroma: f32, lightness_offset: f32) -> Color {
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Self::from_rgb_fractions(
+            r + lightness_offset,
+            g + lightness_offset,
+            b + lightness_offset,
+        )
+    }
+
+    //each component from 0 to 1, converted without a color profile
+    pub fn from_cmyk(cyan: f32, magenta: f32, yellow: f32, black: f32) -> Color {
+        let white = 1.0 - black.clamp(0.0, 1.0);
+        let channel = |value: f32| (1.0 - value.clamp(0.0, 1.0)) * white;
+        Self::from_rgb_fractions(channel(cyan), channel(magenta), channel(yellow))
+    }
+
+    //mixes `percent` of this color with white, like `color!percent!white` in xcolor
+    pub fn tint(&self, percent: u8) -> Color {
+        self.mix(percent, Color::from_hex(0xffffff))
+    }
+
+    //`percent` of this color and the rest of `other`
+    pub fn mix(&self, percent: u8, other: Color) -> Color {
+        let share = percent.min(100) as u32;
+        let mix = |a: u8, b: u8| ((a as u32 * share + b as u32 * (100 - share) + 50) / 100) as u8;
+        Color {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+
+    //the relative luminance as defined by WCAG, from 0 for black to 1 for white
+    pub fn relative_luminance(&self) -> f32 {
+        let linear = |channel: u8| {
+            let value = channel as f32 / 255.0;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    //the WCAG contrast ratio, from 1 for equal colors to 21 for black on white
+    pub fn contrast_ratio(&self, other: Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+//parses `#RRGGBB` and the short form `#RGB`
+impl FromStr for Color {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LatexError::InvalidColor(s.to_owned());
+        let hex = s.trim().strip_prefix('#').ok_or_else(invalid)?;
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+        match hex.len() {
+            6 => Ok(Color {
+                r: channel(&hex[0..2])?,
+                g: channel(&hex[2..4])?,
+                b: channel(&hex[4..6])?,
+            }),
+            //every digit stands for itself twice, `#f80` is `#ff8800`
+            3 => Ok(Color {
+                r: channel(&hex[0..1])? * 17,
+                g: channel(&hex[1..2])? * 17,
+                b: channel(&hex[2..3])? * 17,
+            }),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 //Main KIT Colors
 
-const KIT_GREEN: Color = Color {
+pub const KIT_GREEN: Color = Color {
     r: 0,
     g: 150,
     b: 130,
 }; //#009682
 
-const KIT_BLUE: Color = Color {
+pub const KIT_BLUE: Color = Color {
     r: 70,
     g: 100,
     b: 170,
 }; //#4664AA
 
-const KIT_BLACK: Color = Color { r: 0, g: 0, b: 0 }; //#000000
+pub const KIT_BLACK: Color = Color { r: 0, g: 0, b: 0 }; //#000000
 
-const KIT_BLACK_70: Color = Color {
+pub const KIT_BLACK_70: Color = Color {
     r: 64,
     g: 64,
     b: 64,
@@ -46,43 +169,43 @@ const KIT_BLACK_70: Color = Color {
 
 //More KIT Colors
 
-const KIT_YELLOW: Color = Color {
+pub const KIT_YELLOW: Color = Color {
     r: 252,
     g: 229,
     b: 0,
 }; //#FCE500
 
-const KIT_ORANGE: Color = Color {
+pub const KIT_ORANGE: Color = Color {
     r: 223,
     g: 155,
     b: 27,
 }; //#DF9B1B
 
-const KIT_MAY_GREEN: Color = Color {
+pub const KIT_MAY_GREEN: Color = Color {
     r: 140,
     g: 182,
     b: 60,
 }; //#8CB63C
 
-const KIT_RED: Color = Color {
+pub const KIT_RED: Color = Color {
     r: 162,
     g: 34,
     b: 35,
 }; //#A22223
 
-const KIT_PURPLE: Color = Color {
+pub const KIT_PURPLE: Color = Color {
     r: 163,
     g: 16,
     b: 124,
 }; //#A3107C
 
-const KIT_BROWN: Color = Color {
+pub const KIT_BROWN: Color = Color {
     r: 167,
     g: 130,
     b: 46,
 }; //#A7822E
 
-const KIT_CYAN: Color = Color {
+pub const KIT_CYAN: Color = Color {
     r: 35,
     g: 161,
     b: 224,
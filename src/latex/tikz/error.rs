@@ -2,4 +2,12 @@
 pub enum TikzError {
     NoPoints,
     NotFiniteFloat,
+    //radii and other sizes have to be greater than 0
+    NotPositiveFloat,
+    //opacities go from 0 to 1
+    OpacityOutOfRange,
+    ArrowTipNotConfigurable,
+    //circles, ellipses and rectangles have no ends for arrow tips
+    ArrowOnClosedPath,
+    ConflictingOption(&'static str),
 }
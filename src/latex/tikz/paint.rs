@@ -0,0 +1,342 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use super::{finite, Color, LatexError, LatexResult};
+
+//a color as it is used by an option: a defined rgb color or a standard xcolor name,
+//optionally mixed with further colors like `red!30!white`, and an optional alpha
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paint {
+    base: BaseColor,
+    //each step mixes `percent` of everything before with the color
+    mixes: Vec<(u8, BaseColor)>,
+    alpha: Option<f32>,
+}
+
+impl Paint {
+    pub fn new(base: impl Into<BaseColor>) -> Self {
+        Paint {
+            base: base.into(),
+            mixes: Vec::new(),
+            alpha: None,
+        }
+    }
+
+    //`percent` of this paint and the rest of `other`, the `!percent!other` of xcolor
+    pub fn mix(mut self, percent: u8, other: impl Into<BaseColor>) -> Self {
+        self.mixes.push((percent.min(100), other.into()));
+        self
+    }
+
+    //0 is fully transparent and 1 opaque, only tikz can draw transparent colors
+    pub fn alpha(mut self, alpha: f32) -> Self {
+        self.alpha = Some(alpha.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn get_alpha(&self) -> Option<f32> {
+        self.alpha
+    }
+
+    //how the paint is written in latex, without the alpha
+    pub fn expression(&self) -> String {
+        let mut expression = self.base.name();
+        for (percent, color) in self.mixes.iter() {
+            expression.push_str(&format!("!{percent}!{}", color.name()));
+        }
+        expression
+    }
+
+    //the rgb color the paint ends up as, ignoring the alpha
+    pub fn resolve(&self) -> Color {
+        self.mixes
+            .iter()
+            .fold(self.base.rgb(), |resolved, (percent, color)| {
+                resolved.mix(*percent, color.rgb())
+            })
+    }
+
+    //the rgb colors that have to be defined, named colors are known to xcolor already
+    pub fn get_colors(&self) -> BTreeSet<Color> {
+        std::iter::once(&self.base)
+            .chain(self.mixes.iter().map(|(_, color)| color))
+            .filter_map(|color| match color {
+                BaseColor::Rgb(color) => Some(*color),
+                BaseColor::Named(_) => None,
+            })
+            .collect()
+    }
+
+    //`key=expression` followed by `opacity_key=alpha` if the paint has an alpha
+    pub(crate) fn option(&self, key: &str, opacity_key: &str) -> LatexResult<String> {
+        let mut option = format!("{key}={}", self.expression());
+        if let Some(alpha) = self.alpha {
+            option.push_str(&format!(", {opacity_key}={}", finite(alpha)?));
+        }
+        Ok(option)
+    }
+
+    //for the places outside of tikz, which have no transparency
+    pub(crate) fn opaque_expression(&self) -> LatexResult<String> {
+        match self.alpha {
+            Some(_) => Err(LatexError::AlphaNotSupported),
+            None => Ok(self.expression()),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::new(color)
+    }
+}
+
+impl From<NamedColor> for Paint {
+    fn from(color: NamedColor) -> Self {
+        Paint::new(color)
+    }
+}
+
+//parses `#RRGGBB`, `#RGB`, xcolor names and mixes of them like `red!30!white`,
+//a trailing percentage without a color mixes with white like in xcolor
+impl FromStr for Paint {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LatexError::InvalidColor(s.to_owned());
+        let mut parts = s.split('!');
+        let mut paint = Paint::new(parts.next().ok_or_else(invalid)?.parse::<BaseColor>()?);
+        while let Some(percent) = parts.next() {
+            let percent = percent.trim().parse::<u8>().map_err(|_| invalid())?;
+            if percent > 100 {
+                return Err(invalid());
+            }
+            let other = match parts.next() {
+                Some(other) => other.parse::<BaseColor>()?,
+                None => BaseColor::Named(NamedColor::White),
+            };
+            paint = paint.mix(percent, other);
+        }
+        Ok(paint)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseColor {
+    //gets a `\definecolor`
+    Rgb(Color),
+    Named(NamedColor),
+}
+
+impl BaseColor {
+    fn name(&self) -> String {
+        match self {
+            BaseColor::Rgb(color) => color.name(),
+            BaseColor::Named(color) => color.name().to_owned(),
+        }
+    }
+
+    fn rgb(&self) -> Color {
+        match self {
+            BaseColor::Rgb(color) => *color,
+            BaseColor::Named(color) => color.rgb(),
+        }
+    }
+}
+
+impl From<Color> for BaseColor {
+    fn from(color: Color) -> Self {
+        BaseColor::Rgb(color)
+    }
+}
+
+impl From<NamedColor> for BaseColor {
+    fn from(color: NamedColor) -> Self {
+        BaseColor::Named(color)
+    }
+}
+
+impl FromStr for BaseColor {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('#') {
+            Ok(BaseColor::Rgb(s.parse()?))
+        } else {
+            Ok(BaseColor::Named(s.parse()?))
+        }
+    }
+}
+
+//the colors xcolor always defines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NamedColor {
+    Red,
+    Green,
+    Blue,
+    Cyan,
+    Magenta,
+    Yellow,
+    Black,
+    White,
+    Gray,
+    DarkGray,
+    LightGray,
+    Brown,
+    Lime,
+    Olive,
+    Orange,
+    Pink,
+    Purple,
+    Teal,
+    Violet,
+}
+
+impl NamedColor {
+    const ALL: [NamedColor; 19] = [
+        NamedColor::Red,
+        NamedColor::Green,
+        NamedColor::Blue,
+        NamedColor::Cyan,
+        NamedColor::Magenta,
+        NamedColor::Yellow,
+        NamedColor::Black,
+        NamedColor::White,
+        NamedColor::Gray,
+        NamedColor::DarkGray,
+        NamedColor::LightGray,
+        NamedColor::Brown,
+        NamedColor::Lime,
+        NamedColor::Olive,
+        NamedColor::Orange,
+        NamedColor::Pink,
+        NamedColor::Purple,
+        NamedColor::Teal,
+        NamedColor::Violet,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NamedColor::Red => "red",
+            NamedColor::Green => "green",
+            NamedColor::Blue => "blue",
+            NamedColor::Cyan => "cyan",
+            NamedColor::Magenta => "magenta",
+            NamedColor::Yellow => "yellow",
+            NamedColor::Black => "black",
+            NamedColor::White => "white",
+            NamedColor::Gray => "gray",
+            NamedColor::DarkGray => "darkgray",
+            NamedColor::LightGray => "lightgray",
+            NamedColor::Brown => "brown",
+            NamedColor::Lime => "lime",
+            NamedColor::Olive => "olive",
+            NamedColor::Orange => "orange",
+            NamedColor::Pink => "pink",
+            NamedColor::Purple => "purple",
+            NamedColor::Teal => "teal",
+            NamedColor::Violet => "violet",
+        }
+    }
+
+    //the values from the xcolor documentation
+    pub fn rgb(&self) -> Color {
+        let (r, g, b) = match self {
+            NamedColor::Red => (1.0, 0.0, 0.0),
+            NamedColor::Green => (0.0, 1.0, 0.0),
+            NamedColor::Blue => (0.0, 0.0, 1.0),
+            NamedColor::Cyan => (0.0, 1.0, 1.0),
+            NamedColor::Magenta => (1.0, 0.0, 1.0),
+            NamedColor::Yellow => (1.0, 1.0, 0.0),
+            NamedColor::Black => (0.0, 0.0, 0.0),
+            NamedColor::White => (1.0, 1.0, 1.0),
+            NamedColor::Gray => (0.5, 0.5, 0.5),
+            NamedColor::DarkGray => (0.25, 0.25, 0.25),
+            NamedColor::LightGray => (0.75, 0.75, 0.75),
+            NamedColor::Brown => (0.75, 0.5, 0.25),
+            NamedColor::Lime => (0.75, 1.0, 0.0),
+            NamedColor::Olive => (0.5, 0.5, 0.0),
+            NamedColor::Orange => (1.0, 0.5, 0.0),
+            NamedColor::Pink => (1.0, 0.75, 0.75),
+            NamedColor::Purple => (0.75, 0.0, 0.25),
+            NamedColor::Teal => (0.0, 0.5, 0.5),
+            NamedColor::Violet => (0.5, 0.0, 0.5),
+        };
+        Color::from_rgb_fractions(r, g, b)
+    }
+}
+
+impl FromStr for NamedColor {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NamedColor::ALL
+            .into_iter()
+            .find(|color| color.name() == s)
+            .ok_or_else(|| LatexError::InvalidColor(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NamedColor, Paint};
+    use crate::latex::{
+        AddOption, AddPointByParts, Color, DocumentClass, Latex, LatexError, Polygon, Text, Tikz,
+        ToLatex,
+    };
+
+    #[test]
+    fn colors_parse_convert_and_mix() {
+        let orange = Color {
+            r: 255,
+            g: 136,
+            b: 0,
+        };
+        assert_eq!("#ff8800".parse::<Color>().unwrap(), orange);
+        assert_eq!("#F80".parse::<Color>().unwrap(), orange);
+        assert!(matches!(
+            "#ff88".parse::<Color>(),
+            Err(LatexError::InvalidColor(_))
+        ));
+        assert_eq!(Color::from_hsl(32.0, 1.0, 0.5), orange);
+        assert_eq!(Color::from_hsv(32.0, 1.0, 1.0), orange);
+        assert_eq!(Color::from_cmyk(0.0, 0.4667, 1.0, 0.0), orange);
+
+        let mix: Paint = "red!30!white".parse().unwrap();
+        assert_eq!(mix, Paint::new(NamedColor::Red).mix(30, NamedColor::White));
+        assert_eq!(
+            mix.resolve(),
+            Color {
+                r: 255,
+                g: 179,
+                b: 179
+            }
+        );
+        assert!(mix.get_colors().is_empty());
+
+        let polygon = Polygon::new()
+            .option(Paint::new(orange).mix(50, NamedColor::Black).alpha(0.5))
+            .point(0, 0);
+        let output = Latex::new(DocumentClass::Article)
+            .part(Tikz::new().part(polygon))
+            .part(Text::new().colored(NamedColor::Teal, "named"))
+            .export()
+            .unwrap()
+            .to_string();
+        assert!(
+            output.contains(r"\fill[color=0xff8800!50!black, fill opacity=0.5] (0, 0) -- cycle;")
+        );
+        assert!(output.contains(r"\definecolor{0xff8800}"));
+        assert!(!output.contains(r"\definecolor{teal}"));
+        assert!(output.contains(r"\textcolor{teal}{named}"));
+
+        let transparent_text = Latex::new(DocumentClass::Article)
+            .part(Text::new().colored(Paint::new(orange).alpha(0.5), "a"))
+            .export();
+        assert!(matches!(
+            transparent_text,
+            Err(LatexError::AlphaNotSupported)
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::slice::Iter;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use super::color::{
+    KIT_BLACK, KIT_BLUE, KIT_BROWN, KIT_CYAN, KIT_GREEN, KIT_MAY_GREEN, KIT_ORANGE, KIT_PURPLE,
+    KIT_RED, KIT_YELLOW,
+};
+use super::{Brewer, Color, LatexError, LatexResult};
+
+lazy_static! {
+    static ref PALETTES: Mutex<BTreeMap<String, Palette>> = {
+        let mut palettes = BTreeMap::new();
+        let brewer = Brewer::ALL.into_iter().map(Palette::brewer);
+        for palette in [Palette::kit(), Palette::okabe_ito()]
+            .into_iter()
+            .chain(brewer)
+        {
+            palettes.insert(palette.name.clone(), palette);
+        }
+        Mutex::new(palettes)
+    };
+}
+
+//viridis at 0, 0.1, .., 1
+const VIRIDIS: [u32; 11] = [
+    0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884, 0x44bf70, 0x7ad151,
+    0xbddf26, 0xfde725,
+];
+
+//cividis at 0, 1/9, .., 1
+const CIVIDIS: [u32; 10] = [
+    0x00204d, 0x00336f, 0x39486b, 0x575c6d, 0x707173, 0x8a8779, 0xa69d75, 0xc4b56c, 0xe4cf5b,
+    0xffea46,
+];
+
+//an ordered list of colors, e.g. for the series of a chart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    name: String,
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    pub fn new(
+        name: impl Into<String>,
+        colors: impl IntoIterator<Item = Color>,
+    ) -> LatexResult<Self> {
+        let colors: Vec<Color> = colors.into_iter().collect();
+        if colors.is_empty() {
+            return Err(LatexError::EmptyPalette);
+        }
+        Ok(Palette {
+            name: name.into(),
+            colors,
+        })
+    }
+
+    //the colors of the KIT corporate design, the main colors first
+    pub fn kit() -> Self {
+        Palette {
+            name: "kit".to_owned(),
+            colors: vec![
+                KIT_GREEN,
+                KIT_BLUE,
+                KIT_MAY_GREEN,
+                KIT_YELLOW,
+                KIT_ORANGE,
+                KIT_RED,
+                KIT_PURPLE,
+                KIT_BROWN,
+                KIT_CYAN,
+                KIT_BLACK,
+            ],
+        }
+    }
+
+    //the palette of Okabe and Ito that stays distinguishable with all common color blindnesses
+    pub fn okabe_ito() -> Self {
+        let colors = [
+            0xe69f00, 0x56b4e9, 0x009e73, 0xf0e442, 0x0072b2, 0xd55e00, 0xcc79a7, 0x000000,
+        ];
+        Palette {
+            name: "okabe-ito".to_owned(),
+            colors: colors.into_iter().map(Color::from_hex).collect(),
+        }
+    }
+
+    pub fn brewer(scheme: Brewer) -> Self {
+        Palette {
+            name: scheme.name().to_owned(),
+            colors: scheme
+                .hex_colors()
+                .iter()
+                .copied()
+                .map(Color::from_hex)
+                .collect(),
+        }
+    }
+
+    //`count` evenly spaced colors from dark blue to yellow, perceptually uniform
+    pub fn viridis(count: usize) -> LatexResult<Self> {
+        Self::sample("viridis", &VIRIDIS, count)
+    }
+
+    //like viridis, but also uniform for people with red green color blindness
+    pub fn cividis(count: usize) -> LatexResult<Self> {
+        Self::sample("cividis", &CIVIDIS, count)
+    }
+
+    fn sample(name: &str, stops: &[u32], count: usize) -> LatexResult<Self> {
+        let stops: Vec<Color> = stops.iter().copied().map(Color::from_hex).collect();
+        let colors = (0..count).map(|i| {
+            //a single color is taken from the middle
+            let position = if count == 1 {
+                0.5
+            } else {
+                i as f32 / (count - 1) as f32
+            };
+            let scaled = position * (stops.len() - 1) as f32;
+            let lower = (scaled.floor() as usize).min(stops.len() - 2);
+            let share = ((1.0 - (scaled - lower as f32)) * 100.0).round() as u8;
+            stops[lower].mix(share, stops[lower + 1])
+        });
+        Palette::new(format!("{name}{count}"), colors)
+    }
+
+    //makes the palette available by its name everywhere in the program,
+    //a palette registered under the same name before is replaced
+    pub fn register(self) {
+        let mut palettes = PALETTES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        palettes.insert(self.name.clone(), self);
+    }
+
+    //a registered palette, `kit`, `okabe-ito` and the colorbrewer schemes are always there
+    //unless they were replaced
+    pub fn named(name: &str) -> Option<Palette> {
+        let palettes = PALETTES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        palettes.get(name).cloned()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    //never true, a palette has at least one color
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<Color> {
+        self.colors.get(index).copied()
+    }
+
+    //starts over at the first color after the last one, so any series index has a color
+    pub fn color(&self, index: usize) -> Color {
+        self.colors[index % self.colors.len()]
+    }
+
+    pub fn iter(&self) -> Iter<'_, Color> {
+        self.colors.iter()
+    }
+
+    //the colors over and over again
+    pub fn cycle(&self) -> impl Iterator<Item = Color> + '_ {
+        self.colors.iter().copied().cycle()
+    }
+
+    //every color mixed with white, KIT uses the 70%, 50% and 30% tints
+    pub fn tint(&self, percent: u8) -> Palette {
+        Palette {
+            name: format!("{}!{}", self.name, percent.min(100)),
+            colors: self
+                .colors
+                .iter()
+                .map(|color| color.tint(percent))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Palette;
+    use crate::latex::{Color, LatexError, KIT_BLUE, KIT_GREEN};
+
+    #[test]
+    fn palettes_cycle_and_tint() {
+        let kit = Palette::named("kit").unwrap();
+        assert_eq!(kit.color(0), KIT_GREEN);
+        assert_eq!(kit.get(1), Some(KIT_BLUE));
+        assert_eq!(kit.color(kit.len() + 1), KIT_BLUE);
+        assert_eq!(kit.cycle().nth(kit.len()), Some(KIT_GREEN));
+        assert_eq!(
+            kit.tint(50).color(0),
+            Color {
+                r: 128,
+                g: 203,
+                b: 193
+            }
+        );
+        assert_eq!(kit.tint(100).color(0), KIT_GREEN);
+        assert_eq!(
+            KIT_GREEN.tint(0),
+            Color {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+
+        let red = Color { r: 200, g: 0, b: 0 };
+        Palette::new("test corporate", [red]).unwrap().register();
+        assert_eq!(Palette::named("test corporate").unwrap().color(3), red);
+        assert!(Palette::named("missing").is_none());
+        assert!(matches!(
+            Palette::new("empty", []),
+            Err(LatexError::EmptyPalette)
+        ));
+    }
+}
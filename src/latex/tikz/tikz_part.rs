@@ -1,43 +1,52 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeSet, VecDeque};
 
 use derive_more::From;
 
 use super::{
-    AddOption, AddPoint, AddPointByParts, Color, LatexLines, LatexResult, TikzError, ToLatex,
+    AddOption, AddPoint, AddPointByParts, Color, LatexLines, LatexResult, Lint, NamedColor, Paint,
+    Text, TikzError, ToLatex,
 };
 
 mod node;
-pub use node::{Node, NodeOptions};
+pub use node::{Anchor, Direction, Node, NodeOptions, NodeShape, RelativePosition};
+
+mod arrow;
+pub use arrow::{Arrow, ArrowDirection, ArrowTip};
 
 mod line;
-pub use line::{Line, LineOption};
+pub use line::{DashPattern, Line, LineCap, LineJoin, LineOption};
 
 mod polygon;
 pub use polygon::{Polygon, PolygonOption};
 
+mod shape;
+pub use shape::{Arc, Circle, Ellipse, Rectangle, ShapeOption};
+
 mod visible;
-pub use visible::Visible;
+pub use visible::{OverlayMode, OverlaySpec, Visible};
 
 #[derive(From, Clone)]
 pub enum TikzPart {
     Node(Node),
     Line(Line),
     Polygon(Polygon),
-    Visible(Visible),
+    Circle(Circle),
+    Ellipse(Ellipse),
+    Rectangle(Rectangle),
+    Arc(Arc),
+    Visible(Visible<TikzPart>),
 }
 
 impl TikzPart {
-    pub fn get_colors(&self) -> HashSet<Color> {
+    pub fn get_colors(&self) -> BTreeSet<Color> {
         match self {
-            TikzPart::Node(_) => todo!(),
-            TikzPart::Line(_) => todo!(),
-            TikzPart::Polygon(polygon) => {
-                let mut set = HashSet::new();
-                if let Some(color) = polygon.get_color() {
-                    set.insert(color);
-                }
-                set
-            }
+            TikzPart::Node(node) => node.get_colors(),
+            TikzPart::Line(line) => line.get_colors(),
+            TikzPart::Polygon(polygon) => polygon.get_colors(),
+            TikzPart::Circle(circle) => circle.get_colors(),
+            TikzPart::Ellipse(ellipse) => ellipse.get_colors(),
+            TikzPart::Rectangle(rectangle) => rectangle.get_colors(),
+            TikzPart::Arc(arc) => arc.get_colors(),
             TikzPart::Visible(vis) => vis
                 .iter_inner()
                 .flat_map(|inner| inner.get_colors())
@@ -47,9 +56,64 @@ impl TikzPart {
 }
 
 impl TikzPart {
-    pub fn iter_next_inner(&self) -> Option<impl Iterator<Item = &TikzPart> + DoubleEndedIterator> {
+    //the libraries this part needs itself, not its inner parts
+    pub fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        match self {
+            TikzPart::Node(node) => node.tikz_libraries(),
+            TikzPart::Line(line) => line.tikz_libraries(),
+            TikzPart::Arc(arc) => arc.tikz_libraries(),
+            TikzPart::Polygon(_)
+            | TikzPart::Circle(_)
+            | TikzPart::Ellipse(_)
+            | TikzPart::Rectangle(_)
+            | TikzPart::Visible(_) => BTreeSet::new(),
+        }
+    }
+
+    //the texts held by this part itself, not its inner parts
+    pub fn texts(&self) -> Vec<&Text> {
+        match self {
+            TikzPart::Node(node) => vec![node.text()],
+            _ => Vec::new(),
+        }
+    }
+
+    //the lints of this part itself, not its inner parts
+    pub fn lints(&self) -> Vec<Lint> {
+        match self {
+            TikzPart::Node(node) => node.lints(),
+            TikzPart::Line(_)
+            | TikzPart::Polygon(_)
+            | TikzPart::Circle(_)
+            | TikzPart::Ellipse(_)
+            | TikzPart::Rectangle(_)
+            | TikzPart::Arc(_)
+            | TikzPart::Visible(_) => Vec::new(),
+        }
+    }
+
+    pub fn option_conflict(&self) -> Option<&'static str> {
+        match self {
+            TikzPart::Node(node) => node.option_conflict(),
+            TikzPart::Line(line) => line.option_conflict(),
+            TikzPart::Polygon(polygon) => polygon.option_conflict(),
+            TikzPart::Circle(circle) => circle.option_conflict(),
+            TikzPart::Ellipse(ellipse) => ellipse.option_conflict(),
+            TikzPart::Rectangle(rectangle) => rectangle.option_conflict(),
+            TikzPart::Arc(arc) => arc.option_conflict(),
+            TikzPart::Visible(_) => None,
+        }
+    }
+
+    pub fn iter_next_inner(&self) -> Option<impl DoubleEndedIterator<Item = &TikzPart>> {
         match self {
-            TikzPart::Line(_) | TikzPart::Polygon(_) | TikzPart::Node(_) => None,
+            TikzPart::Line(_)
+            | TikzPart::Polygon(_)
+            | TikzPart::Node(_)
+            | TikzPart::Circle(_)
+            | TikzPart::Ellipse(_)
+            | TikzPart::Rectangle(_)
+            | TikzPart::Arc(_) => None,
             TikzPart::Visible(vis) => Some(vis.iter_inner()),
         }
     }
@@ -64,9 +128,13 @@ impl TikzPart {
 impl ToLatex for TikzPart {
     fn export(&self) -> LatexResult<LatexLines> {
         match self {
-            TikzPart::Node(_) => todo!(),
-            TikzPart::Line(_) => todo!(),
+            TikzPart::Node(node) => node.export(),
+            TikzPart::Line(line) => line.export(),
             TikzPart::Polygon(polygon) => polygon.export(),
+            TikzPart::Circle(circle) => circle.export(),
+            TikzPart::Ellipse(ellipse) => ellipse.export(),
+            TikzPart::Rectangle(rectangle) => rectangle.export(),
+            TikzPart::Arc(arc) => arc.export(),
             TikzPart::Visible(vis) => vis.export(),
         }
     }
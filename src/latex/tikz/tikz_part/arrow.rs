@@ -0,0 +1,245 @@
+use super::{Paint, TikzError};
+use crate::latex::tikz::finite;
+use crate::latex::{LatexLines, LatexResult, ToLatex};
+
+//arrow tips for any path, exported as the `->`-like part of the path options
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arrow {
+    direction: ArrowDirection,
+    tip: ArrowTip,
+    //tip length in pt
+    size: Option<f32>,
+    color: Option<Paint>,
+}
+
+impl Arrow {
+    pub fn new(direction: ArrowDirection) -> Self {
+        Arrow {
+            direction,
+            tip: ArrowTip::default(),
+            size: None,
+            color: None,
+        }
+    }
+
+    pub fn start() -> Self {
+        Self::new(ArrowDirection::Start)
+    }
+
+    pub fn end() -> Self {
+        Self::new(ArrowDirection::End)
+    }
+
+    pub fn both() -> Self {
+        Self::new(ArrowDirection::Both)
+    }
+
+    pub fn tip(mut self, tip: ArrowTip) -> Self {
+        self.tip = tip;
+        self
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    //tips follow the opacity of their line, so the paint can not have an alpha
+    pub fn color(mut self, color: impl Into<Paint>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn get_color(&self) -> Option<&Paint> {
+        self.color.as_ref()
+    }
+
+    pub fn tikz_library(&self) -> &'static str {
+        self.tip.library()
+    }
+
+    fn export_tip(&self) -> LatexResult<String> {
+        let name = self.tip.name();
+        if !self.tip.is_meta() {
+            return if self.size.is_some() || self.color.is_some() {
+                Err(TikzError::ArrowTipNotConfigurable.into())
+            } else {
+                Ok(name.to_owned())
+            };
+        }
+
+        let mut options = Vec::new();
+        if let Some(size) = self.size {
+            options.push(format!("length={}pt", finite(size)?));
+        }
+        if let Some(color) = &self.color {
+            options.push(format!("color={}", color.opaque_expression()?));
+        }
+
+        if options.is_empty() {
+            Ok(format!("{{{name}}}"))
+        } else {
+            Ok(format!("{{{name}[{}]}}", options.join(", ")))
+        }
+    }
+}
+
+impl ToLatex for Arrow {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let tip = self.export_tip()?;
+        let arrow = match self.direction {
+            ArrowDirection::Start => format!("{tip}-"),
+            ArrowDirection::End => format!("-{tip}"),
+            ArrowDirection::Both => format!("{tip}-{tip}"),
+        };
+        Ok(vec![arrow].into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowDirection {
+    Start,
+    End,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrowTip {
+    //tips of the `arrows.meta` library, these can be sized and colored
+    #[default]
+    To,
+    Stealth,
+    Latex,
+    Triangle,
+    Circle,
+    Square,
+    Kite,
+    Bar,
+    Hooks,
+    //tips of the older `arrows` library
+    StealthPrime,
+    LatexPrime,
+    Triangle45,
+    Triangle60,
+    Triangle90,
+    Angle45,
+    Angle60,
+    Angle90,
+    Diamond,
+}
+
+impl ArrowTip {
+    fn name(&self) -> &'static str {
+        match self {
+            ArrowTip::To => "To",
+            ArrowTip::Stealth => "Stealth",
+            ArrowTip::Latex => "Latex",
+            ArrowTip::Triangle => "Triangle",
+            ArrowTip::Circle => "Circle",
+            ArrowTip::Square => "Square",
+            ArrowTip::Kite => "Kite",
+            ArrowTip::Bar => "Bar",
+            ArrowTip::Hooks => "Hooks",
+            ArrowTip::StealthPrime => "stealth'",
+            ArrowTip::LatexPrime => "latex'",
+            ArrowTip::Triangle45 => "triangle 45",
+            ArrowTip::Triangle60 => "triangle 60",
+            ArrowTip::Triangle90 => "triangle 90",
+            ArrowTip::Angle45 => "angle 45",
+            ArrowTip::Angle60 => "angle 60",
+            ArrowTip::Angle90 => "angle 90",
+            ArrowTip::Diamond => "diamond",
+        }
+    }
+
+    fn library(&self) -> &'static str {
+        if self.is_meta() {
+            "arrows.meta"
+        } else {
+            "arrows"
+        }
+    }
+
+    fn is_meta(&self) -> bool {
+        matches!(
+            self,
+            ArrowTip::To
+                | ArrowTip::Stealth
+                | ArrowTip::Latex
+                | ArrowTip::Triangle
+                | ArrowTip::Circle
+                | ArrowTip::Square
+                | ArrowTip::Kite
+                | ArrowTip::Bar
+                | ArrowTip::Hooks
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arrow, ArrowTip};
+    use crate::latex::{
+        AddOption, AddPointByParts, Color, LatexError, LatexResult, Line, TikzError, ToLatex,
+    };
+
+    fn export(arrow: Arrow) -> LatexResult<String> {
+        Ok(arrow.export()?.to_string())
+    }
+
+    #[test]
+    fn tips_are_exported() {
+        let red = Color { r: 255, g: 0, b: 0 };
+        assert_eq!(export(Arrow::end()).unwrap(), "-{To}");
+        assert_eq!(
+            export(Arrow::start().tip(ArrowTip::Stealth).size(4.0)).unwrap(),
+            "{Stealth[length=4pt]}-"
+        );
+        assert_eq!(
+            export(Arrow::both().tip(ArrowTip::Latex).size(3.0).color(red)).unwrap(),
+            "{Latex[length=3pt, color=0xff0000]}-{Latex[length=3pt, color=0xff0000]}"
+        );
+        assert_eq!(
+            export(Arrow::end().tip(ArrowTip::StealthPrime)).unwrap(),
+            "-stealth'"
+        );
+
+        let line = Line::new()
+            .option(Arrow::end().tip(ArrowTip::Kite))
+            .point(0, 0)
+            .point(1, 0);
+        assert_eq!(
+            line.export().unwrap().to_string(),
+            r"\draw[-{Kite}] (0, 0) -- (1, 0);"
+        );
+    }
+
+    #[test]
+    fn tips_choose_their_library() {
+        assert_eq!(Arrow::end().tikz_library(), "arrows.meta");
+        assert_eq!(
+            Arrow::end().tip(ArrowTip::Hooks).tikz_library(),
+            "arrows.meta"
+        );
+        assert_eq!(
+            Arrow::end().tip(ArrowTip::Triangle45).tikz_library(),
+            "arrows"
+        );
+    }
+
+    #[test]
+    fn old_tips_can_not_be_configured() {
+        let sized = Arrow::end().tip(ArrowTip::Angle60).size(2.0);
+        assert!(matches!(
+            export(sized),
+            Err(LatexError::Tikz(TikzError::ArrowTipNotConfigurable))
+        ));
+        let colored = Arrow::end()
+            .tip(ArrowTip::Diamond)
+            .color(Color { r: 0, g: 0, b: 255 });
+        assert!(matches!(
+            export(colored),
+            Err(LatexError::Tikz(TikzError::ArrowTipNotConfigurable))
+        ));
+    }
+}
@@ -1,16 +1,17 @@
-use super::{AddOption, AddPoint, AddPointByParts, Color, TikzError};
-use crate::latex::{LatexLines, LatexResult, ToLatex};
+use super::{AddOption, AddPoint, AddPointByParts, Arrow, Color, NamedColor, Paint, TikzError};
+use crate::latex::tikz::{finite, opacity};
+use crate::latex::{LatexLines, LatexResult, NamedOption, OptionSet, ToLatex};
 use derive_more::From;
 use simple_math::Vec2;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fmt::Write;
-use std::hash::{Hash, Hasher};
-use std::mem::discriminant;
 
+//an open path through all points, or a closed one if `closed` is set
 #[derive(Default, Clone)]
 pub struct Line {
     points: Vec<Vec2>,
-    options: HashSet<LineOption>,
+    options: OptionSet<LineOption>,
+    closed: bool,
 }
 
 impl Line {
@@ -18,14 +19,36 @@ impl Line {
         Self::default()
     }
 
-    pub(super) fn get_color(&self) -> Option<Color> {
-        self.options.iter().find_map(|o| {
-            if let LineOption::Color(color) = o {
-                Some(*color)
-            } else {
-                None
-            }
-        })
+    //connects the last point back to the first one with `-- cycle`
+    pub fn closed(mut self) -> Self {
+        self.closed = true;
+        self
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        self.options
+            .iter()
+            .filter_map(|o| match o {
+                LineOption::Color(paint) => Some(paint.get_colors()),
+                LineOption::Arrow(arrow) => arrow.get_color().map(|paint| paint.get_colors()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    pub(super) fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        self.options
+            .iter()
+            .filter_map(|o| match o {
+                LineOption::Arrow(arrow) => Some(arrow.tikz_library()),
+                _ => None,
+            })
+            .collect()
     }
 }
 
@@ -47,19 +70,20 @@ impl<T: Into<LineOption>> AddOption<T> for Line {
 impl ToLatex for Line {
     fn export(&self) -> LatexResult<LatexLines> {
         let mut latex = String::new();
-        write!(&mut latex, "\\fill[")?;
-        for option in self.options.iter() {
-            write!(&mut latex, "{}", option.export()?)?;
-        }
-        write!(&mut latex, "]")?;
+        write!(&mut latex, "\\draw[")?;
+        write!(&mut latex, "{}]", self.options.export_list()?)?;
 
         match &self.points[..] {
             [] => return Err(TikzError::NoPoints.into()),
-            [points @ ..] => {
-                for point in points {
-                    write!(&mut latex, " {} --", point.export()?)?;
+            [first, rest @ ..] => {
+                write!(&mut latex, " {}", first.export()?)?;
+                for point in rest {
+                    write!(&mut latex, " -- {}", point.export()?)?;
                 }
-                write!(&mut latex, " cycle;")?;
+                if self.closed {
+                    write!(&mut latex, " -- cycle")?;
+                }
+                write!(&mut latex, ";")?;
             }
         }
 
@@ -67,29 +91,253 @@ impl ToLatex for Line {
     }
 }
 
-#[derive(Debug, Clone, Copy, From)]
+#[derive(Debug, Clone, PartialEq, From)]
 pub enum LineOption {
-    Color(Color),
+    //the alpha of the paint becomes the draw opacity
+    Color(Paint),
+    //in pt
+    #[from(ignore)]
+    Width(f32),
+    Dash(DashPattern),
+    Cap(LineCap),
+    Join(LineJoin),
+    #[from(ignore)]
+    Opacity(f32),
+    Arrow(Arrow),
+}
+
+impl LineOption {
+    //the names shapes use for the same stroke options
+    pub(super) const WIDTH: &'static str = "line width";
+    pub(super) const DASH: &'static str = "dash pattern";
+    pub(super) const ARROWS: &'static str = "arrows";
+    const OPACITY: &'static str = "draw opacity";
 }
 
 impl ToLatex for LineOption {
     fn export(&self) -> LatexResult<LatexLines> {
+        let option = match self {
+            LineOption::Color(paint) => paint.option("color", LineOption::OPACITY)?,
+            LineOption::Width(width) => format!("line width={}pt", finite(*width)?),
+            LineOption::Dash(dash) => dash.export()?.to_string(),
+            LineOption::Cap(cap) => format!("line cap={}", cap.name()),
+            LineOption::Join(join) => format!("line join={}", join.name()),
+            LineOption::Opacity(value) => format!("{}={}", LineOption::OPACITY, opacity(*value)?),
+            LineOption::Arrow(arrow) => arrow.export()?.to_string(),
+        };
+        Ok(vec![option].into())
+    }
+}
+
+impl From<Color> for LineOption {
+    fn from(color: Color) -> Self {
+        LineOption::Color(color.into())
+    }
+}
+
+impl From<NamedColor> for LineOption {
+    fn from(color: NamedColor) -> Self {
+        LineOption::Color(color.into())
+    }
+}
+
+impl NamedOption for LineOption {
+    fn name(&self) -> &'static str {
+        match self {
+            LineOption::Color(_) => "color",
+            LineOption::Width(_) => LineOption::WIDTH,
+            LineOption::Dash(_) => LineOption::DASH,
+            LineOption::Cap(_) => "line cap",
+            LineOption::Join(_) => "line join",
+            LineOption::Opacity(_) => LineOption::OPACITY,
+            LineOption::Arrow(_) => LineOption::ARROWS,
+        }
+    }
+
+    //a color with alpha sets the opacity as well
+    fn keys(&self) -> Vec<&'static str> {
+        match self {
+            LineOption::Color(paint) if paint.get_alpha().is_some() => {
+                vec![self.name(), LineOption::OPACITY]
+            }
+            _ => vec![self.name()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DashPattern {
+    Solid,
+    Dashed,
+    DenselyDashed,
+    LooselyDashed,
+    Dotted,
+    DenselyDotted,
+    LooselyDotted,
+    DashDotted,
+    //on and off lengths in pt
+    Custom { on: f32, off: f32 },
+}
+
+impl ToLatex for DashPattern {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let pattern = match self {
+            DashPattern::Solid => "solid".to_owned(),
+            DashPattern::Dashed => "dashed".to_owned(),
+            DashPattern::DenselyDashed => "densely dashed".to_owned(),
+            DashPattern::LooselyDashed => "loosely dashed".to_owned(),
+            DashPattern::Dotted => "dotted".to_owned(),
+            DashPattern::DenselyDotted => "densely dotted".to_owned(),
+            DashPattern::LooselyDotted => "loosely dotted".to_owned(),
+            DashPattern::DashDotted => "dash dot".to_owned(),
+            DashPattern::Custom { on, off } => {
+                format!("dash pattern=on {}pt off {}pt", finite(*on)?, finite(*off)?)
+            }
+        };
+        Ok(vec![pattern].into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Rect,
+}
+
+impl LineCap {
+    fn name(&self) -> &'static str {
         match self {
-            LineOption::Color(color) => Ok(vec![format!("color={}", color.name())].into()),
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Rect => "rect",
         }
     }
 }
 
-impl PartialEq for LineOption {
-    fn eq(&self, other: &Self) -> bool {
-        discriminant(self) == discriminant(other)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoin {
+    fn name(&self) -> &'static str {
+        match self {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        }
     }
 }
 
-impl Eq for LineOption {}
+#[cfg(test)]
+mod tests {
+    use super::{DashPattern, Line, LineCap, LineJoin, LineOption};
+    use crate::latex::{AddOption, AddPointByParts, Color, LatexError, Paint, TikzError, ToLatex};
+
+    fn export(option: impl Into<LineOption>) -> String {
+        let line = Line::new().option(option).point(0, 0).point(2, 1);
+        line.export().unwrap().to_string()
+    }
+
+    #[test]
+    fn paths_are_open_or_closed() {
+        let open = Line::new().point(0, 0).point(1, 0).point(1, 1);
+        assert_eq!(
+            open.export().unwrap().to_string(),
+            r"\draw[] (0, 0) -- (1, 0) -- (1, 1);"
+        );
+        let closed = open.closed();
+        assert_eq!(
+            closed.export().unwrap().to_string(),
+            r"\draw[] (0, 0) -- (1, 0) -- (1, 1) -- cycle;"
+        );
+        assert!(matches!(
+            Line::new().export(),
+            Err(LatexError::Tikz(TikzError::NoPoints))
+        ));
+    }
+
+    #[test]
+    fn stroke_options_are_exported() {
+        let red = Color { r: 255, g: 0, b: 0 };
+        assert_eq!(export(red), r"\draw[color=0xff0000] (0, 0) -- (2, 1);");
+        assert_eq!(
+            export(LineOption::Width(0.8)),
+            r"\draw[line width=0.8pt] (0, 0) -- (2, 1);"
+        );
+        assert_eq!(
+            export(DashPattern::Custom { on: 2.0, off: 1.0 }),
+            r"\draw[dash pattern=on 2pt off 1pt] (0, 0) -- (2, 1);"
+        );
+        assert_eq!(
+            export(DashPattern::DenselyDotted),
+            r"\draw[densely dotted] (0, 0) -- (2, 1);"
+        );
+        assert_eq!(
+            export(LineCap::Round),
+            r"\draw[line cap=round] (0, 0) -- (2, 1);"
+        );
+        assert_eq!(
+            export(LineJoin::Bevel),
+            r"\draw[line join=bevel] (0, 0) -- (2, 1);"
+        );
+        assert_eq!(
+            export(LineOption::Opacity(0.5)),
+            r"\draw[draw opacity=0.5] (0, 0) -- (2, 1);"
+        );
 
-impl Hash for LineOption {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        discriminant(self).hash(state);
+        let infinite = Line::new().option(LineOption::Width(f32::NAN)).point(0, 0);
+        assert!(matches!(
+            infinite.export(),
+            Err(LatexError::Tikz(TikzError::NotFiniteFloat))
+        ));
+    }
+
+    #[test]
+    fn options_set_twice_conflict() {
+        let line = Line::new().option(LineCap::Round).option(LineCap::Butt);
+        assert_eq!(line.option_conflict(), Some("line cap"));
+        let line = line.point(0, 0);
+        assert_eq!(
+            line.export().unwrap().to_string(),
+            r"\draw[line cap=butt] (0, 0);"
+        );
+
+        let same = Line::new().option(LineJoin::Miter).option(LineJoin::Miter);
+        assert_eq!(same.option_conflict(), None);
+
+        let red = Color { r: 255, g: 0, b: 0 };
+        let faint = Line::new()
+            .option(Paint::new(red).alpha(0.5))
+            .option(LineOption::Opacity(0.3));
+        assert_eq!(faint.option_conflict(), Some("draw opacity"));
+        let faint = Line::new()
+            .option(LineOption::Opacity(0.3))
+            .option(Paint::new(red).alpha(0.5));
+        assert_eq!(faint.option_conflict(), Some("draw opacity"));
+        let opaque = Line::new().option(LineOption::Opacity(0.3)).option(red);
+        assert_eq!(opaque.option_conflict(), None);
+    }
+
+    #[test]
+    fn opacity_is_checked() {
+        let export = |value: f32| {
+            let line = Line::new().option(LineOption::Opacity(value)).point(0, 0);
+            line.export()
+        };
+        assert!(export(0.0).is_ok() && export(1.0).is_ok());
+        for value in [-0.1, 1.5] {
+            assert!(matches!(
+                export(value),
+                Err(LatexError::Tikz(TikzError::OpacityOutOfRange))
+            ));
+        }
+        assert!(matches!(
+            export(f32::NAN),
+            Err(LatexError::Tikz(TikzError::NotFiniteFloat))
+        ));
     }
 }
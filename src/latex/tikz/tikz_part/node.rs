@@ -1,5 +1,398 @@
-#[derive(Clone)]
+use super::{AddOption, Color, Paint};
+use crate::latex::tikz::finite;
+use crate::latex::{
+    FontSize, LatexLines, LatexResult, Lint, NamedColor, NamedOption, OptionSet, Text, ToLatex,
+};
+use derive_more::From;
+use simple_math::Vec2;
+use std::collections::BTreeSet;
+use std::fmt::Write;
 
-pub struct Node {}
+#[derive(Default, Clone)]
+pub struct Node {
+    name: Option<String>,
+    position: Option<Vec2>,
+    text: Text,
+    options: OptionSet<NodeOptions>,
+}
 
-pub enum NodeOptions {}
+impl Node {
+    //plain strings are escaped like any other text
+    pub fn new(text: impl Into<Text>) -> Self {
+        Node {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    //the name other nodes use to be placed relative to this one
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn at(mut self, position: impl Into<Vec2>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    pub(super) fn text(&self) -> &Text {
+        &self.text
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        self.options
+            .iter()
+            .filter_map(|o| match o {
+                NodeOptions::Fill(paint) | NodeOptions::Draw(paint) | NodeOptions::Text(paint) => {
+                    Some(paint.get_colors())
+                }
+                _ => None,
+            })
+            .flatten()
+            .chain(self.text.get_colors())
+            .collect()
+    }
+
+    //the text is black and the background white if they are not set
+    pub(super) fn lints(&self) -> Vec<Lint> {
+        let (mut text, mut fill) = (None, None);
+        for option in self.options.iter() {
+            match option {
+                NodeOptions::Text(paint) => text = Some(paint.clone()),
+                NodeOptions::Fill(paint) => fill = Some(paint.clone()),
+                _ => {}
+            }
+        }
+        if self.text.is_empty() || (text.is_none() && fill.is_none()) {
+            return Vec::new();
+        }
+        let text = text.unwrap_or_else(|| NamedColor::Black.into());
+        let fill = fill.unwrap_or_else(|| NamedColor::White.into());
+        let location = match &self.name {
+            Some(name) => format!("node ({name})"),
+            None => format!("node \"{}\"", self.text.plain()),
+        };
+        Lint::contrast(location, &text, &fill).into_iter().collect()
+    }
+
+    pub(super) fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        self.options
+            .iter()
+            .filter_map(|o| match o {
+                NodeOptions::Shape(shape) => shape.library(),
+                NodeOptions::Position(position) => position.library(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl<T: Into<NodeOptions>> AddOption<T> for Node {
+    fn option(mut self, option: T) -> Self {
+        self.options.insert(option.into());
+        self
+    }
+}
+
+impl ToLatex for Node {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut latex = String::new();
+        write!(&mut latex, "\\node[")?;
+        write!(&mut latex, "{}]", self.options.export_list()?)?;
+
+        if let Some(name) = &self.name {
+            write!(&mut latex, " ({name})")?;
+        }
+        if let Some(position) = &self.position {
+            write!(&mut latex, " at {}", position.export()?)?;
+        }
+        write!(&mut latex, " {{{}}};", self.text.export()?)?;
+
+        Ok(vec![latex].into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, From)]
+pub enum NodeOptions {
+    Shape(NodeShape),
+    Anchor(Anchor),
+    //in pt
+    #[from(ignore)]
+    InnerSep(f32),
+    //in pt
+    #[from(ignore)]
+    OuterSep(f32),
+    //in cm
+    #[from(ignore)]
+    MinimumSize(f32),
+    //the alphas of the paints become the fill, draw and text opacity
+    #[from(ignore)]
+    Fill(Paint),
+    #[from(ignore)]
+    Draw(Paint),
+    #[from(ignore)]
+    Text(Paint),
+    FontSize(FontSize),
+    Position(RelativePosition),
+}
+
+impl ToLatex for NodeOptions {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let option = match self {
+            NodeOptions::Shape(shape) => shape.name().to_owned(),
+            NodeOptions::Anchor(anchor) => format!("anchor={}", anchor.name()),
+            NodeOptions::InnerSep(sep) => format!("inner sep={}pt", finite(*sep)?),
+            NodeOptions::OuterSep(sep) => format!("outer sep={}pt", finite(*sep)?),
+            NodeOptions::MinimumSize(size) => format!("minimum size={}cm", finite(*size)?),
+            NodeOptions::Fill(paint) => paint.option("fill", "fill opacity")?,
+            NodeOptions::Draw(paint) => paint.option("draw", "draw opacity")?,
+            NodeOptions::Text(paint) => paint.option("text", "text opacity")?,
+            NodeOptions::FontSize(size) => format!("font={}", size.command()),
+            NodeOptions::Position(position) => position.export()?.to_string(),
+        };
+        Ok(vec![option].into())
+    }
+}
+
+impl NamedOption for NodeOptions {
+    fn name(&self) -> &'static str {
+        match self {
+            NodeOptions::Shape(_) => "shape",
+            NodeOptions::Anchor(_) => "anchor",
+            NodeOptions::InnerSep(_) => "inner sep",
+            NodeOptions::OuterSep(_) => "outer sep",
+            NodeOptions::MinimumSize(_) => "minimum size",
+            NodeOptions::Fill(_) => "fill",
+            NodeOptions::Draw(_) => "draw",
+            NodeOptions::Text(_) => "text",
+            NodeOptions::FontSize(_) => "font",
+            NodeOptions::Position(_) => "position",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeShape {
+    Rectangle,
+    Circle,
+    //needs the `shapes.geometric` library
+    Ellipse,
+    //needs the `shapes.misc` library
+    RoundedRectangle,
+}
+
+impl NodeShape {
+    fn name(&self) -> &'static str {
+        match self {
+            NodeShape::Rectangle => "rectangle",
+            NodeShape::Circle => "circle",
+            NodeShape::Ellipse => "ellipse",
+            NodeShape::RoundedRectangle => "rounded rectangle",
+        }
+    }
+
+    fn library(&self) -> Option<&'static str> {
+        match self {
+            NodeShape::Rectangle | NodeShape::Circle => None,
+            NodeShape::Ellipse => Some("shapes.geometric"),
+            NodeShape::RoundedRectangle => Some("shapes.misc"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    Center,
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+    Base,
+}
+
+impl Anchor {
+    fn name(&self) -> &'static str {
+        match self {
+            Anchor::Center => "center",
+            Anchor::North => "north",
+            Anchor::South => "south",
+            Anchor::East => "east",
+            Anchor::West => "west",
+            Anchor::NorthEast => "north east",
+            Anchor::NorthWest => "north west",
+            Anchor::SouthEast => "south east",
+            Anchor::SouthWest => "south west",
+            Anchor::Base => "base",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Above,
+    Below,
+    Left,
+    Right,
+    AboveLeft,
+    AboveRight,
+    BelowLeft,
+    BelowRight,
+}
+
+impl Direction {
+    fn name(&self) -> &'static str {
+        match self {
+            Direction::Above => "above",
+            Direction::Below => "below",
+            Direction::Left => "left",
+            Direction::Right => "right",
+            Direction::AboveLeft => "above left",
+            Direction::AboveRight => "above right",
+            Direction::BelowLeft => "below left",
+            Direction::BelowRight => "below right",
+        }
+    }
+}
+
+//places a node relative to another named node
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativePosition {
+    direction: Direction,
+    of: String,
+    //in cm
+    distance: Option<f32>,
+    //`right=of a` from the `positioning` library instead of `right of=a`
+    positioning: bool,
+}
+
+impl RelativePosition {
+    //the plain tikz syntax `above of=node`
+    pub fn of(direction: Direction, node: impl Into<String>) -> Self {
+        RelativePosition {
+            direction,
+            of: node.into(),
+            distance: None,
+            positioning: false,
+        }
+    }
+
+    //the `positioning` library syntax `above=of node`, measuring from the node borders
+    pub fn positioning(direction: Direction, node: impl Into<String>) -> Self {
+        RelativePosition {
+            positioning: true,
+            ..Self::of(direction, node)
+        }
+    }
+
+    fn library(&self) -> Option<&'static str> {
+        self.positioning.then_some("positioning")
+    }
+
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = Some(distance);
+        self
+    }
+}
+
+impl ToLatex for RelativePosition {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let direction = self.direction.name();
+        let of = &self.of;
+        let position = match (self.positioning, self.distance) {
+            (true, None) => format!("{direction}=of {of}"),
+            (true, Some(distance)) => format!("{direction}={}cm of {of}", finite(distance)?),
+            (false, None) => format!("{direction} of={of}"),
+            (false, Some(distance)) => {
+                format!("{direction} of={of}, node distance={}cm", finite(distance)?)
+            }
+        };
+        Ok(vec![position].into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Anchor, Direction, Node, NodeOptions, NodeShape, RelativePosition};
+    use crate::latex::{AddOption, Color, FontSize, Lint, NamedColor, Text, ToLatex};
+
+    fn export(node: Node) -> String {
+        node.export().unwrap().to_string()
+    }
+
+    #[test]
+    fn nodes_are_named_and_anchored() {
+        let node = Node::new("a")
+            .name("a")
+            .at((1, 2))
+            .option(Anchor::NorthEast);
+        assert_eq!(export(node), r"\node[anchor=north east] (a) at (1, 2) {a};");
+        let shaped = Node::new("c").option(NodeShape::Ellipse);
+        assert!(shaped.tikz_libraries().contains("shapes.geometric"));
+        assert_eq!(export(shaped), r"\node[ellipse] {c};");
+        assert_eq!(
+            export(Node::new("d").option(NodeOptions::InnerSep(2.0))),
+            r"\node[inner sep=2pt] {d};"
+        );
+        assert_eq!(
+            export(Node::new("e").option(FontSize::Small)),
+            r"\node[font=\small] {e};"
+        );
+        let red = Color { r: 255, g: 0, b: 0 };
+        assert_eq!(
+            export(Node::new("f").option(NodeOptions::Fill(red.into()))),
+            r"\node[fill=0xff0000] {f};"
+        );
+    }
+
+    #[test]
+    fn nodes_are_placed_relative_to_others() {
+        let of = Node::new("b")
+            .name("b")
+            .option(RelativePosition::of(Direction::Right, "a"));
+        assert!(of.tikz_libraries().is_empty());
+        assert_eq!(export(of), r"\node[right of=a] (b) {b};");
+        let apart =
+            Node::new("b").option(RelativePosition::of(Direction::Below, "a").distance(2.0));
+        assert_eq!(export(apart), r"\node[below of=a, node distance=2cm] {b};");
+
+        let positioned = Node::new("c")
+            .name("c")
+            .option(RelativePosition::positioning(Direction::AboveLeft, "b"));
+        assert!(positioned.tikz_libraries().contains("positioning"));
+        assert_eq!(export(positioned), r"\node[above left=of b] (c) {c};");
+        let apart = Node::new("c")
+            .option(RelativePosition::positioning(Direction::BelowRight, "b").distance(1.5));
+        assert_eq!(export(apart), r"\node[below right=1.5cm of b] {c};");
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let node = Node::new("50% x_1").option(NodeOptions::Fill(NamedColor::Yellow.into()));
+        assert_eq!(
+            node.export().unwrap().to_string(),
+            r"\node[fill=yellow] {50\% x\_1};"
+        );
+        let math = Node::new(Text::new().raw("$x_1$"));
+        assert_eq!(math.export().unwrap().to_string(), r"\node[] {$x_1$};");
+
+        let faint = node.option(NodeOptions::Text(NamedColor::White.into()));
+        assert!(matches!(
+            &faint.lints()[..],
+            [Lint::LowContrast { location, .. }] if location == r#"node "50% x_1""#
+        ));
+        let named = faint.name("a");
+        assert!(matches!(
+            &named.lints()[..],
+            [Lint::LowContrast { location, .. }] if location == "node (a)"
+        ));
+    }
+}
@@ -1,16 +1,13 @@
-use super::{AddOption, AddPoint, AddPointByParts, Color, TikzError};
-use crate::latex::{LatexLines, LatexResult, ToLatex};
-use derive_more::From;
+use super::{AddOption, AddPoint, AddPointByParts, Color, NamedColor, Paint, TikzError};
+use crate::latex::{LatexLines, LatexResult, NamedOption, OptionSet, ToLatex};
 use simple_math::Vec2;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fmt::Write;
-use std::hash::{Hash, Hasher};
-use std::mem::discriminant;
 
 #[derive(Default, Clone)]
 pub struct Polygon {
     points: Vec<Vec2>,
-    options: HashSet<PolygonOption>,
+    options: OptionSet<PolygonOption>,
 }
 
 impl Polygon {
@@ -18,14 +15,17 @@ impl Polygon {
         Self::default()
     }
 
-    pub(super) fn get_color(&self) -> Option<Color> {
-        self.options.iter().find_map(|o| {
-            if let PolygonOption::Color(color) = o {
-                Some(*color)
-            } else {
-                None
-            }
-        })
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        self.options
+            .iter()
+            .flat_map(|o| match o {
+                PolygonOption::Color(paint) => paint.get_colors(),
+            })
+            .collect()
     }
 }
 
@@ -48,10 +48,7 @@ impl ToLatex for Polygon {
     fn export(&self) -> LatexResult<LatexLines> {
         let mut latex = String::new();
         write!(&mut latex, "\\fill[")?;
-        for option in self.options.iter() {
-            write!(&mut latex, "{}", option.export()?)?;
-        }
-        write!(&mut latex, "]")?;
+        write!(&mut latex, "{}]", self.options.export_list()?)?;
 
         match &self.points[..] {
             [] => return Err(TikzError::NoPoints.into()),
@@ -67,29 +64,42 @@ impl ToLatex for Polygon {
     }
 }
 
-#[derive(Debug, Clone, Copy, From)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PolygonOption {
-    Color(Color),
+    //the alpha of the paint becomes the fill opacity
+    Color(Paint),
 }
 
-impl ToLatex for PolygonOption {
-    fn export(&self) -> LatexResult<LatexLines> {
-        match self {
-            PolygonOption::Color(color) => Ok(vec![format!("color={}", color.name())].into()),
-        }
+impl From<Paint> for PolygonOption {
+    fn from(paint: Paint) -> Self {
+        PolygonOption::Color(paint)
     }
 }
 
-impl PartialEq for PolygonOption {
-    fn eq(&self, other: &Self) -> bool {
-        discriminant(self) == discriminant(other)
+impl From<Color> for PolygonOption {
+    fn from(color: Color) -> Self {
+        PolygonOption::Color(color.into())
     }
 }
 
-impl Eq for PolygonOption {}
+impl From<NamedColor> for PolygonOption {
+    fn from(color: NamedColor) -> Self {
+        PolygonOption::Color(color.into())
+    }
+}
 
-impl Hash for PolygonOption {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        discriminant(self).hash(state);
+impl ToLatex for PolygonOption {
+    fn export(&self) -> LatexResult<LatexLines> {
+        match self {
+            PolygonOption::Color(paint) => Ok(vec![paint.option("color", "fill opacity")?].into()),
+        }
+    }
+}
+
+impl NamedOption for PolygonOption {
+    fn name(&self) -> &'static str {
+        match self {
+            PolygonOption::Color(_) => "color",
+        }
     }
 }
@@ -0,0 +1,415 @@
+use super::{AddOption, Arrow, Color, DashPattern, LineOption, Paint, TikzError};
+use crate::latex::tikz::{finite, positive};
+use crate::latex::{LatexLines, LatexResult, NamedOption, OptionSet, ToLatex};
+use derive_more::From;
+use simple_math::Vec2;
+use std::collections::BTreeSet;
+
+//the options of circles, ellipses, rectangles and arcs,
+//a shape without fill and draw is drawn in the default color
+#[derive(Debug, Clone, PartialEq, From)]
+pub enum ShapeOption {
+    //the alpha of the paint becomes the fill opacity
+    #[from(ignore)]
+    Fill(Paint),
+    //the alpha of the paint becomes the draw opacity
+    #[from(ignore)]
+    Draw(Paint),
+    //in pt
+    #[from(ignore)]
+    Width(f32),
+    Dash(DashPattern),
+    //only arcs have ends to put tips on
+    Arrow(Arrow),
+}
+
+impl ToLatex for ShapeOption {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let option = match self {
+            ShapeOption::Fill(paint) => paint.option("fill", "fill opacity")?,
+            ShapeOption::Draw(paint) => paint.option("draw", "draw opacity")?,
+            ShapeOption::Width(width) => format!("line width={}pt", finite(*width)?),
+            ShapeOption::Dash(dash) => dash.export()?.to_string(),
+            ShapeOption::Arrow(arrow) => arrow.export()?.to_string(),
+        };
+        Ok(vec![option].into())
+    }
+}
+
+impl NamedOption for ShapeOption {
+    fn name(&self) -> &'static str {
+        match self {
+            ShapeOption::Fill(_) => "fill",
+            ShapeOption::Draw(_) => "draw",
+            ShapeOption::Width(_) => LineOption::WIDTH,
+            ShapeOption::Dash(_) => LineOption::DASH,
+            ShapeOption::Arrow(_) => LineOption::ARROWS,
+        }
+    }
+}
+
+fn get_colors(options: &OptionSet<ShapeOption>) -> BTreeSet<Color> {
+    options
+        .iter()
+        .filter_map(|o| match o {
+            ShapeOption::Fill(paint) | ShapeOption::Draw(paint) => Some(paint.get_colors()),
+            ShapeOption::Arrow(arrow) => arrow.get_color().map(|paint| paint.get_colors()),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+fn tikz_libraries(options: &OptionSet<ShapeOption>) -> BTreeSet<&'static str> {
+    options
+        .iter()
+        .filter_map(|o| match o {
+            ShapeOption::Arrow(arrow) => Some(arrow.tikz_library()),
+            _ => None,
+        })
+        .collect()
+}
+
+//fails for arrows on the closed shapes
+fn check_closed(options: &OptionSet<ShapeOption>) -> LatexResult<()> {
+    match options.iter().any(|o| matches!(o, ShapeOption::Arrow(_))) {
+        true => Err(TikzError::ArrowOnClosedPath.into()),
+        false => Ok(()),
+    }
+}
+
+//`\fill`, `\draw` or `\filldraw` depending on the options, followed by the path
+fn export_shape(
+    options: &OptionSet<ShapeOption>,
+    extra_options: &[String],
+    path: String,
+) -> LatexResult<LatexLines> {
+    let fill = options.iter().any(|o| matches!(o, ShapeOption::Fill(_)));
+    let draw = options.iter().any(|o| matches!(o, ShapeOption::Draw(_)));
+    let command = match (fill, draw) {
+        (true, false) => "fill",
+        (true, true) => "filldraw",
+        (false, _) => "draw",
+    };
+
+    let mut list = Vec::new();
+    if !options.is_empty() {
+        list.push(options.export_list()?);
+    }
+    list.extend(extra_options.iter().cloned());
+    Ok(vec![format!("\\{command}[{}] {path};", list.join(", "))].into())
+}
+
+#[derive(Clone)]
+pub struct Circle {
+    center: Vec2,
+    radius: f32,
+    options: OptionSet<ShapeOption>,
+}
+
+impl Circle {
+    pub fn new(center: impl Into<Vec2>, radius: f32) -> Self {
+        Circle {
+            center: center.into(),
+            radius,
+            options: OptionSet::new(),
+        }
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        get_colors(&self.options)
+    }
+}
+
+impl<T: Into<ShapeOption>> AddOption<T> for Circle {
+    fn option(mut self, option: T) -> Self {
+        self.options.insert(option.into());
+        self
+    }
+}
+
+impl ToLatex for Circle {
+    fn export(&self) -> LatexResult<LatexLines> {
+        check_closed(&self.options)?;
+        let path = format!(
+            "{} circle [radius={}]",
+            self.center.export()?,
+            positive(self.radius)?
+        );
+        export_shape(&self.options, &[], path)
+    }
+}
+
+#[derive(Clone)]
+pub struct Ellipse {
+    center: Vec2,
+    x_radius: f32,
+    y_radius: f32,
+    options: OptionSet<ShapeOption>,
+}
+
+impl Ellipse {
+    pub fn new(center: impl Into<Vec2>, x_radius: f32, y_radius: f32) -> Self {
+        Ellipse {
+            center: center.into(),
+            x_radius,
+            y_radius,
+            options: OptionSet::new(),
+        }
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        get_colors(&self.options)
+    }
+}
+
+impl<T: Into<ShapeOption>> AddOption<T> for Ellipse {
+    fn option(mut self, option: T) -> Self {
+        self.options.insert(option.into());
+        self
+    }
+}
+
+impl ToLatex for Ellipse {
+    fn export(&self) -> LatexResult<LatexLines> {
+        check_closed(&self.options)?;
+        let path = format!(
+            "{} ellipse [x radius={}, y radius={}]",
+            self.center.export()?,
+            positive(self.x_radius)?,
+            positive(self.y_radius)?
+        );
+        export_shape(&self.options, &[], path)
+    }
+}
+
+#[derive(Clone)]
+pub struct Rectangle {
+    from: Vec2,
+    to: Vec2,
+    //in cm
+    rounded_corners: Option<f32>,
+    options: OptionSet<ShapeOption>,
+}
+
+impl Rectangle {
+    //spanned by two opposite corners
+    pub fn new(from: impl Into<Vec2>, to: impl Into<Vec2>) -> Self {
+        Rectangle {
+            from: from.into(),
+            to: to.into(),
+            rounded_corners: None,
+            options: OptionSet::new(),
+        }
+    }
+
+    pub fn rounded_corners(mut self, radius: f32) -> Self {
+        self.rounded_corners = Some(radius);
+        self
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        get_colors(&self.options)
+    }
+}
+
+impl<T: Into<ShapeOption>> AddOption<T> for Rectangle {
+    fn option(mut self, option: T) -> Self {
+        self.options.insert(option.into());
+        self
+    }
+}
+
+impl ToLatex for Rectangle {
+    fn export(&self) -> LatexResult<LatexLines> {
+        check_closed(&self.options)?;
+        let mut extra_options = Vec::new();
+        if let Some(radius) = self.rounded_corners {
+            extra_options.push(format!("rounded corners={}cm", positive(radius)?));
+        }
+        let path = format!("{} rectangle {}", self.from.export()?, self.to.export()?);
+        export_shape(&self.options, &extra_options, path)
+    }
+}
+
+//a part of a circle, counterclockwise from the start to the end angle
+#[derive(Clone)]
+pub struct Arc {
+    center: Vec2,
+    radius: f32,
+    //in degrees, 0 is to the right
+    start_angle: f32,
+    end_angle: f32,
+    options: OptionSet<ShapeOption>,
+}
+
+impl Arc {
+    pub fn new(center: impl Into<Vec2>, radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        Arc {
+            center: center.into(),
+            radius,
+            start_angle,
+            end_angle,
+            options: OptionSet::new(),
+        }
+    }
+
+    pub(super) fn option_conflict(&self) -> Option<&'static str> {
+        self.options.conflict()
+    }
+
+    pub(super) fn get_colors(&self) -> BTreeSet<Color> {
+        get_colors(&self.options)
+    }
+
+    pub(super) fn tikz_libraries(&self) -> BTreeSet<&'static str> {
+        tikz_libraries(&self.options)
+    }
+}
+
+impl<T: Into<ShapeOption>> AddOption<T> for Arc {
+    fn option(mut self, option: T) -> Self {
+        self.options.insert(option.into());
+        self
+    }
+}
+
+impl ToLatex for Arc {
+    fn export(&self) -> LatexResult<LatexLines> {
+        //tikz starts an arc at the current point, not at the center
+        let radius = positive(self.radius)? as f64;
+        let start_angle = finite(self.start_angle)?;
+        let (sin, cos) = (start_angle as f64).to_radians().sin_cos();
+        //rounded, so that e.g. cos(90) does not end up as 6e-17, which tikz cannot read
+        let round = |value: f64| (value * 1e6).round() / 1e6;
+        let start = Vec2::from((
+            round(self.center.x() + radius * cos),
+            round(self.center.y() + radius * sin),
+        ));
+        let path = format!(
+            "{} arc [start angle={}, end angle={}, radius={}]",
+            start.export()?,
+            start_angle,
+            finite(self.end_angle)?,
+            self.radius
+        );
+        export_shape(&self.options, &[], path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{Arc, Circle, Ellipse, Rectangle, ShapeOption};
+    use crate::latex::{
+        AddOption, AddPointByParts, Arrow, Color, DashPattern, LatexError, LatexLines, LatexPart,
+        LatexResult, Line, NamedColor, Paint, Tikz, TikzError, ToLatex,
+    };
+
+    #[test]
+    fn shapes_are_drawn_and_filled() {
+        let red = Color { r: 200, g: 0, b: 0 };
+        let tikz = Tikz::new()
+            .part(Circle::new((1, 1), 0.5))
+            .part(Circle::new((0, 0), 2.0).option(ShapeOption::Fill(Paint::new(red).alpha(0.5))))
+            .part(
+                Ellipse::new((0, 0), 2.0, 1.0)
+                    .option(ShapeOption::Fill(NamedColor::Blue.into()))
+                    .option(ShapeOption::Draw(red.into()))
+                    .option(ShapeOption::Width(2.0)),
+            )
+            .part(
+                Rectangle::new((0, 0), (3, 2))
+                    .rounded_corners(0.2)
+                    .option(DashPattern::Dashed),
+            )
+            .part(Arc::new((0, 0), 1.0, 90.0, 180.0).option(ShapeOption::Draw(red.into())));
+        let output = tikz.export().unwrap().to_string();
+        let expected = r"\begin{tikzpicture}[]
+	\draw[] (1, 1) circle [radius=0.5];
+	\fill[fill=0xc80000, fill opacity=0.5] (0, 0) circle [radius=2];
+	\filldraw[fill=blue, draw=0xc80000, line width=2pt] (0, 0) ellipse [x radius=2, y radius=1];
+	\draw[dashed, rounded corners=0.2cm] (0, 0) rectangle (3, 2);
+	\draw[draw=0xc80000] (0, 1) arc [start angle=90, end angle=180, radius=1];
+\end{tikzpicture}";
+        assert_eq!(output, expected);
+        assert_eq!(LatexPart::from(tikz).get_colors(), BTreeSet::from([red]));
+
+        let conflicting = Tikz::new().part(
+            Circle::new((0, 0), 1.0)
+                .option(ShapeOption::Fill(red.into()))
+                .option(ShapeOption::Fill(NamedColor::Blue.into())),
+        );
+        assert!(matches!(
+            conflicting.check_conflicts(),
+            Err(LatexError::Tikz(TikzError::ConflictingOption("fill")))
+        ));
+    }
+
+    #[test]
+    fn arcs_take_arrows() {
+        let arc = Arc::new((0, 0), 2.0, 0.0, 90.0).option(Arrow::end().color(NamedColor::Red));
+        assert_eq!(
+            arc.export().unwrap().to_string(),
+            r"\draw[-{To[color=red]}] (2, 0) arc [start angle=0, end angle=90, radius=2];"
+        );
+        assert!(arc.tikz_libraries().contains("arrows.meta"));
+
+        let circle = Circle::new((0, 0), 1.0)
+            .option(ShapeOption::Draw(NamedColor::Red.into()))
+            .option(Arrow::end());
+        assert!(matches!(
+            circle.export(),
+            Err(LatexError::Tikz(TikzError::ArrowOnClosedPath))
+        ));
+    }
+
+    #[test]
+    fn sizes_are_positive() {
+        let not_positive = |export: LatexResult<LatexLines>| {
+            matches!(export, Err(LatexError::Tikz(TikzError::NotPositiveFloat)))
+        };
+        for size in [0.0, -1.0] {
+            assert!(not_positive(Circle::new((0, 0), size).export()));
+            assert!(not_positive(Ellipse::new((0, 0), 1.0, size).export()));
+            assert!(not_positive(Arc::new((0, 0), size, 0.0, 90.0).export()));
+            let rectangle = Rectangle::new((0, 0), (1, 1)).rounded_corners(size);
+            assert!(not_positive(rectangle.export()));
+        }
+        assert!(matches!(
+            Circle::new((0, 0), f32::NAN).export(),
+            Err(LatexError::Tikz(TikzError::NotFiniteFloat))
+        ));
+    }
+
+    #[test]
+    fn stroke_options_are_named_like_for_lines() {
+        let circle = Circle::new((0, 0), 1.0)
+            .option(ShapeOption::Width(1.0))
+            .option(ShapeOption::Width(2.0));
+        assert_eq!(circle.option_conflict(), Some("line width"));
+        let dashed = Rectangle::new((0, 0), (1, 1))
+            .option(DashPattern::Dashed)
+            .option(DashPattern::Dotted);
+        assert_eq!(dashed.option_conflict(), Some("dash pattern"));
+        let line = Line::new()
+            .point(0, 0)
+            .point(1, 0)
+            .option(DashPattern::Dashed)
+            .option(DashPattern::Dotted);
+        assert_eq!(line.option_conflict(), dashed.option_conflict());
+    }
+}
@@ -1,23 +1,300 @@
-use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::RangeInclusive;
+use std::slice::Iter;
+use std::str::FromStr;
 
-use crate::latex::ToLatex;
+use crate::latex::{LatexError, ToLatex};
 
-use super::{LatexLines, LatexResult, TikzPart};
+use super::{LatexLines, LatexResult};
 
+//beamer overlay around tikz parts or whole latex parts
 #[derive(Clone)]
-pub struct Visible {
-    frames: HashSet<u32>,
-    inner: Vec<TikzPart>,
+pub struct Visible<T> {
+    mode: OverlayMode,
+    spec: OverlaySpec,
+    inner: Vec<T>,
 }
 
-impl Visible {
-    pub fn iter_inner(&self) -> impl Iterator<Item = &TikzPart> + DoubleEndedIterator {
+impl<T> Visible<T> {
+    pub fn new(spec: OverlaySpec) -> Self {
+        Visible {
+            mode: OverlayMode::Visible,
+            spec,
+            inner: Vec::new(),
+        }
+    }
+
+    pub fn mode(mut self, mode: OverlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn part(mut self, part: impl Into<T>) -> Self {
+        self.inner.push(part.into());
+        self
+    }
+
+    pub fn iter_inner(&self) -> Iter<'_, T> {
         self.inner.iter()
     }
 }
 
-impl ToLatex for Visible {
+impl<T: ToLatex> ToLatex for Visible<T> {
+    fn export(&self) -> LatexResult<LatexLines> {
+        let mut lines = Vec::new();
+        lines.push(format!("{}<{}>{{", self.mode.command(), self.spec.export()?).into());
+        for inner in self.inner.iter() {
+            let mut inner_lines = inner.export()?;
+            for mut inner_line in inner_lines.drain(..) {
+                inner_line.indentation += 1;
+                lines.push(inner_line);
+            }
+        }
+        lines.push("}".to_owned().into());
+
+        Ok(lines.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayMode {
+    //hidden but still taking up space
+    Visible,
+    //not there at all on other slides
+    Only,
+    //shown transparent on other slides if beamer is configured that way
+    Uncover,
+}
+
+impl OverlayMode {
+    fn command(&self) -> &'static str {
+        match self {
+            OverlayMode::Visible => r"\visible",
+            OverlayMode::Only => r"\only",
+            OverlayMode::Uncover => r"\uncover",
+        }
+    }
+}
+
+//the slides something is shown on, e.g. `1,3-5,7-`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OverlaySpec {
+    //sorted, neither overlapping nor touching each other or `onwards`
+    ranges: Vec<RangeInclusive<u32>>,
+    //every slide from this one on
+    onwards: Option<u32>,
+}
+
+impl OverlaySpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn frame(self, frame: u32) -> Self {
+        self.frames(frame..=frame)
+    }
+
+    pub fn frames(mut self, frames: RangeInclusive<u32>) -> Self {
+        if frames.is_empty() {
+            return self;
+        }
+        let (mut start, mut end) = frames.into_inner();
+        //merge every range overlapping or touching the new one
+        self.ranges.retain(|range| {
+            let touching =
+                *range.start() <= end.saturating_add(1) && start <= range.end().saturating_add(1);
+            if touching {
+                start = start.min(*range.start());
+                end = end.max(*range.end());
+            }
+            !touching
+        });
+        let index = self.ranges.partition_point(|range| *range.start() < start);
+        self.ranges.insert(index, start..=end);
+        self.absorb();
+        self
+    }
+
+    pub fn onwards(mut self, frame: u32) -> Self {
+        self.onwards = Some(self.onwards.map_or(frame, |onwards| onwards.min(frame)));
+        self.absorb();
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty() && self.onwards.is_none()
+    }
+
+    //lets the open range swallow the closed ranges reaching it
+    fn absorb(&mut self) {
+        let Some(mut onwards) = self.onwards else {
+            return;
+        };
+        while let Some(range) = self.ranges.last() {
+            if range.end().saturating_add(1) < onwards {
+                break;
+            }
+            onwards = onwards.min(*range.start());
+            self.ranges.pop();
+        }
+        self.onwards = Some(onwards);
+    }
+}
+
+impl Display for OverlaySpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut parts: Vec<String> = self
+            .ranges
+            .iter()
+            .map(|range| {
+                if range.start() == range.end() {
+                    format!("{}", range.start())
+                } else {
+                    format!("{}-{}", range.start(), range.end())
+                }
+            })
+            .collect();
+        if let Some(onwards) = self.onwards {
+            parts.push(format!("{onwards}-"));
+        }
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+impl ToLatex for OverlaySpec {
     fn export(&self) -> LatexResult<LatexLines> {
-        todo!()
+        if self.is_empty() {
+            Err(LatexError::EmptyOverlaySpec)
+        } else if self.ranges.first().is_some_and(|range| *range.start() == 0)
+            || self.onwards == Some(0)
+        {
+            //beamer counts slides from 1
+            Err(LatexError::InvalidOverlaySpec(self.to_string()))
+        } else {
+            Ok(vec![self.to_string()].into())
+        }
+    }
+}
+
+impl FromStr for OverlaySpec {
+    type Err = LatexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LatexError::InvalidOverlaySpec(s.to_owned());
+        let parse = |frame: &str| match frame.trim().parse::<u32>() {
+            Ok(0) | Err(_) => Err(invalid()),
+            Ok(frame) => Ok(frame),
+        };
+
+        let mut spec = OverlaySpec::new();
+        for part in s.split(',') {
+            spec = match part.split_once('-') {
+                None => spec.frame(parse(part)?),
+                Some((start, end)) if end.trim().is_empty() => spec.onwards(parse(start)?),
+                Some((start, end)) => {
+                    let (start, end) = (parse(start)?, parse(end)?);
+                    if start > end {
+                        return Err(invalid());
+                    }
+                    spec.frames(start..=end)
+                }
+            };
+        }
+
+        if spec.is_empty() {
+            Err(invalid())
+        } else {
+            Ok(spec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OverlayMode, OverlaySpec, Visible};
+    use crate::latex::{LatexError, LatexPart, Node, TikzPart, ToLatex};
+
+    fn compact(spec: &str) -> String {
+        spec.parse::<OverlaySpec>().unwrap().to_string()
+    }
+
+    #[test]
+    fn parts_are_wrapped_in_the_overlay_command() {
+        let node = Visible::<TikzPart>::new(OverlaySpec::new().frame(2)).part(Node::new("a"));
+        assert_eq!(
+            node.export().unwrap().to_string(),
+            "\\visible<2>{\n\t\\node[] {a};\n}"
+        );
+        let center = Visible::<LatexPart>::new("1,3-".parse().unwrap())
+            .mode(OverlayMode::Only)
+            .part(LatexPart::Center(Vec::new()));
+        assert_eq!(
+            center.export().unwrap().to_string(),
+            "\\only<1,3->{\n\t\\begin{center}\n\t\\end{center}\n}"
+        );
+        let uncover =
+            Visible::<TikzPart>::new(OverlaySpec::new().frames(1..=2)).mode(OverlayMode::Uncover);
+        assert_eq!(uncover.export().unwrap().to_string(), "\\uncover<1-2>{\n}");
+    }
+
+    #[test]
+    fn specs_are_compacted() {
+        assert_eq!(compact("1,2,3,5-"), "1-3,5-");
+        assert_eq!(compact("3,1,2"), "1-3");
+        assert_eq!(compact("1,3-5,7"), "1,3-5,7");
+    }
+
+    #[test]
+    fn open_ranges_absorb_what_follows() {
+        assert_eq!(compact("4-"), "4-");
+        assert_eq!(compact("2-3,4-"), "2-");
+        assert_eq!(compact("6,8-10,5-"), "5-");
+        assert_eq!(compact("7-,3-"), "3-");
+        let built = OverlaySpec::new().onwards(4).frames(1..=3).frame(9);
+        assert_eq!(built.to_string(), "1-");
+    }
+
+    #[test]
+    fn overlapping_ranges_merge() {
+        assert_eq!(compact("1-4,3-6"), "1-6");
+        assert_eq!(compact("2-5,2-5,4"), "2-5");
+        assert_eq!(compact("1-2,4-6,3"), "1-6");
+    }
+
+    #[test]
+    fn long_ranges_are_not_expanded() {
+        assert_eq!(compact("1-4000000000"), "1-4000000000");
+        assert_eq!(compact("3-4294967295,1-2"), "1-4294967295");
+        let built = OverlaySpec::new().frames(2..=u32::MAX).onwards(5).frame(1);
+        assert_eq!(built.to_string(), "1-");
+    }
+
+    #[test]
+    fn specs_round_trip_through_display() {
+        for spec in ["1", "2-", "1,3", "1-3,5-", "2,4-6,9-"] {
+            assert_eq!(compact(spec), spec);
+            assert_eq!(compact(&compact(spec)), spec);
+        }
+    }
+
+    #[test]
+    fn malformed_specs_are_rejected() {
+        for spec in ["", "a", "1,,2", "-3", "1-2-3", "3-1", "0", "0-2", "1,0-"] {
+            assert!(
+                matches!(
+                    spec.parse::<OverlaySpec>(),
+                    Err(LatexError::InvalidOverlaySpec(_))
+                ),
+                "{spec}"
+            );
+        }
+        assert!(matches!(
+            OverlaySpec::new().export(),
+            Err(LatexError::EmptyOverlaySpec)
+        ));
+        assert!(matches!(
+            OverlaySpec::new().frame(0).export(),
+            Err(LatexError::InvalidOverlaySpec(_))
+        ));
     }
 }
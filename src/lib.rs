@@ -0,0 +1,8 @@
+#![allow(dead_code)]
+#![allow(irrefutable_let_patterns)]
+#![allow(unreachable_patterns)]
+
+//lets `::latex_creater` paths, like the ones `ToTableRow` expands to, work inside this crate too
+extern crate self as latex_creater;
+
+pub mod latex;
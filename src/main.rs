@@ -5,12 +5,12 @@
 #![allow(unreachable_patterns)]
 #![feature(exit_status_error)]
 
-mod latex;
 use std::{path::Path, str::from_utf8};
 
-use latex::{AddOption, AddPointByParts, Color, DocumentClass, Latex, LatexPart, Polygon, Tikz};
-
-use crate::latex::{TikzOption, ToLatex};
+use latex_creater::latex::{
+    AddOption, AddPointByParts, Color, DocumentClass, Frame, Latex, LatexPart, Polygon, Tikz,
+    TikzOption, ToLatex,
+};
 
 fn main() {
     let poly = Polygon::new()
@@ -27,7 +27,7 @@ fn main() {
     let tikz = Tikz::new().part(poly).option(TikzOption::Scale(0.3));
 
     let center = LatexPart::Center(vec![tikz.into()]);
-    let frame = LatexPart::Frame(vec![center]);
+    let frame = Frame::new().part(center);
 
     let latex = Latex::new(DocumentClass::Beamer).part(frame);
 